@@ -1,10 +1,17 @@
 use log::{error, info, Level};
 use worker::{event, Env, Result, ScheduleContext, ScheduledEvent};
 
-use crate::{lil_nouns::LilNouns, meta_gov::MetaGov, prop_house::PropHouse, prop_lot::PropLot};
+use crate::{
+  lil_nouns::LilNouns,
+  linker::Linker,
+  meta_gov::{MetaGov, Space},
+  prop_house::{Community, PropHouse},
+  prop_lot::PropLot,
+};
 
 mod cache;
 mod lil_nouns;
+mod linker;
 mod meta_gov;
 mod prop_house;
 mod prop_lot;
@@ -23,24 +30,42 @@ async fn start(env: &Env) -> Result<()> {
   };
 
   if env.var("META_GOV_ENABLED").unwrap().to_string() == "true" {
-    match MetaGov::new_from_env(env) {
-      Ok(result) => match result.start().await {
-        Ok(_) => info!("MetaGov started successfully"),
-        Err(error) => error!("Failed to start MetaGov: {:?}", error),
-      },
+    match Space::list_from_env(env) {
+      Ok(spaces) => {
+        for space in spaces {
+          let space_id = space.id.clone();
+          match MetaGov::new_from_env(env, space) {
+            Ok(result) => match result.start().await {
+              Ok(_) => info!("MetaGov started successfully ({})", space_id),
+              Err(error) => error!("Failed to start MetaGov ({}): {:?}", space_id, error),
+            },
+
+            Err(error) => error!("Failed to create MetaGov ({}): {:?}", space_id, error),
+          }
+        }
+      }
 
-      Err(error) => error!("Failed to create MetaGov: {:?}", error),
+      Err(error) => error!("Failed to read MetaGov spaces: {:?}", error),
     }
   };
 
   if env.var("PROP_HOUSE_ENABLED").unwrap().to_string() == "true" {
-    match PropHouse::new_from_env(env) {
-      Ok(result) => match result.start().await {
-        Ok(_) => info!("PropHouse started successfully"),
-        Err(error) => error!("Failed to start PropHouse: {:?}", error),
-      },
+    match Community::list_from_env(env).await {
+      Ok(communities) => {
+        for community in communities {
+          let community_id = community.id;
+          match PropHouse::new_from_env(env, community) {
+            Ok(result) => match result.start().await {
+              Ok(_) => info!("PropHouse started successfully ({})", community_id),
+              Err(error) => error!("Failed to start PropHouse ({}): {:?}", community_id, error),
+            },
+
+            Err(error) => error!("Failed to create PropHouse ({}): {:?}", community_id, error),
+          }
+        }
+      }
 
-      Err(error) => error!("Failed to create PropHouse: {:?}", error),
+      Err(error) => error!("Failed to read PropHouse communities: {:?}", error),
     }
   }
 
@@ -55,6 +80,17 @@ async fn start(env: &Env) -> Result<()> {
     }
   }
 
+  if env.var("LINKER_ENABLED").unwrap().to_string() == "true" {
+    match Linker::new_from_env(env).await {
+      Ok(result) => match result.start().await {
+        Ok(_) => info!("Linker started successfully"),
+        Err(error) => error!("Failed to start Linker: {:?}", error),
+      },
+
+      Err(error) => error!("Failed to create Linker: {:?}", error),
+    }
+  }
+
   Ok(())
 }
 
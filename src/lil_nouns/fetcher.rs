@@ -66,6 +66,7 @@ impl GraphQLFetcher {
         id: proposal.id.parse::<usize>().unwrap(),
         title: proposal.title.clone(),
         proposer: proposal.proposer.id.clone(),
+        created: proposal.created_timestamp.parse().unwrap_or_default(),
       })
       .collect();
 
@@ -16,6 +16,9 @@ pub struct Proposal {
   pub id: usize,
   pub title: String,
   pub proposer: String,
+  /// Unix timestamp, left at zero on proposals cached before it was tracked.
+  #[serde(default)]
+  pub created: i64,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use chrono::Local;
+use header::CONTENT_TYPE;
+use log::{error, info};
+use reqwest::{header, Client};
+use serde_json::{json, Value};
+use worker::{Env, Error, Result};
+
+use crate::{
+  linker::{handler::Handler, Proposal},
+  prop_lot::Idea,
+};
+
+pub(crate) struct DiscordHandler {
+  base_url: String,
+  webhook_url: String,
+  client: Client,
+}
+
+impl DiscordHandler {
+  pub fn new(base_url: String, webhook_url: String, client: Client) -> Self {
+    Self {
+      base_url,
+      webhook_url,
+      client,
+    }
+  }
+
+  pub fn new_from_env(env: &Env) -> Result<DiscordHandler> {
+    let base_url = env.var("PROP_LOT_BASE_URL")?.to_string();
+    let webhook_url = env.secret("PROP_LOT_DISCORD_WEBHOOK_URL")?.to_string();
+
+    let client = Client::new();
+
+    Ok(Self::new(base_url, webhook_url, client))
+  }
+
+  async fn execute_webhook(&self, embed: Value) -> Result<()> {
+    let msg_json = json!({
+      "username": "Raven",
+      "avatar_url": "https://i.imgur.com/qP2QpJq.png",
+      "embeds": [embed]
+    });
+
+    self
+      .client
+      .post(&self.webhook_url)
+      .header(CONTENT_TYPE, "application/json")
+      .body(msg_json.to_string())
+      .send()
+      .await
+      .map_err(|e| {
+        error!("Failed to execute webhook: {}", e);
+        Error::from(format!("Failed to execute webhook: {}", e))
+      })?;
+
+    Ok(())
+  }
+}
+
+#[async_trait(? Send)]
+impl Handler for DiscordHandler {
+  async fn handle_idea_link(&self, idea: &Idea, proposal: &Proposal) -> Result<()> {
+    info!("Handling idea link: {}", proposal.key);
+
+    let idea_url = format!("{}/idea/{}", self.base_url, idea.id);
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let description = format!(
+      "[Idea #{}]({}) “{}” is now {}: [“{}”]({})",
+      idea.id, idea_url, idea.title, proposal.destination, proposal.title, proposal.url
+    );
+
+    let embed = json!({
+        "title": "Prop Lot Idea Became a Proposal",
+        "description": description,
+        "url": proposal.url,
+        "color": 0xFFB911,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+}
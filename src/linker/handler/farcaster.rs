@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use log::{debug, error, info};
+use reqwest::{
+  header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE},
+  Client,
+  Response,
+};
+use serde_json::{json, Value};
+use worker::{Env, Error, Result};
+
+use crate::{
+  cache::Cache,
+  linker::{handler::Handler, Proposal, Source},
+  prop_lot::{Channel, Idea},
+  utils::text::truncate_bytes,
+};
+
+pub(crate) struct FarcasterHandler {
+  base_url: String,
+  warpcast_url: String,
+  bearer_token: String,
+  /// Tokens of the sources replies in proposal threads are cast with.
+  source_tokens: HashMap<Source, String>,
+  channels: Vec<Channel>,
+  cache: Cache,
+  client: Client,
+}
+
+impl FarcasterHandler {
+  pub fn new(
+    base_url: String,
+    warpcast_url: String,
+    bearer_token: String,
+    source_tokens: HashMap<Source, String>,
+    channels: Vec<Channel>,
+    cache: Cache,
+    client: Client,
+  ) -> Self {
+    Self {
+      base_url,
+      warpcast_url,
+      bearer_token,
+      source_tokens,
+      channels,
+      cache,
+      client,
+    }
+  }
+
+  pub fn new_from_env(env: &Env) -> Result<FarcasterHandler> {
+    let base_url = env.var("PROP_LOT_BASE_URL")?.to_string();
+    let warpcast_url = env.var("WARP_CAST_API_BASE_URL")?.to_string();
+    let bearer_token = env.secret("PROP_LOT_WARP_CAST_TOKEN")?.to_string();
+    let channels = Channel::list_from_env(env)?;
+
+    // Sources without Farcaster credentials have no thread to reply in.
+    let source_tokens = Source::ALL
+      .into_iter()
+      .filter_map(|source| {
+        let token = env.secret(source.warp_cast_token_secret()).ok()?;
+        Some((source, token.to_string()))
+      })
+      .collect();
+
+    let cache = Cache::new_from_env(env);
+    let client = Client::new();
+
+    Ok(Self::new(
+      base_url,
+      warpcast_url,
+      bearer_token,
+      source_tokens,
+      channels,
+      cache,
+      client,
+    ))
+  }
+
+  async fn make_http_request(&self, bearer_token: &str, request_data: Value) -> Result<Response> {
+    let url = format!("{}/casts", self.warpcast_url);
+    let token = format!("Bearer {}", bearer_token);
+    let mut headers = HeaderMap::new();
+
+    let parsed_token =
+      HeaderValue::from_str(&token).map_err(|_| Error::from("Error while parsing token"))?;
+
+    headers.insert(AUTHORIZATION, parsed_token);
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    // Send the HTTP POST request
+    let response = self
+      .client
+      .post(url)
+      .headers(headers)
+      .json(&request_data)
+      .send()
+      .await
+      .map_err(|e| {
+        error!("Failed to execute request: {}", e);
+        Error::from(format!("Failed to execute request: {}", e))
+      })?;
+
+    debug!("Response status: {:?}", response.status());
+
+    Ok(response)
+  }
+
+  async fn post_cast(
+    &self,
+    bearer_token: &str,
+    channel_key: &str,
+    text: &str,
+    url: &str,
+    parent: Option<String>,
+  ) -> Result<()> {
+    let mut request_data = json!({
+      "text": truncate_bytes(text, 320),
+      "embeds": [url],
+      "channelKey": channel_key
+    });
+    if let Some(parent) = parent {
+      request_data["parent"] = json!({"hash": parent});
+    }
+
+    self.make_http_request(bearer_token, request_data).await?;
+
+    Ok(())
+  }
+
+  async fn get_cast_hash(&self, key: &str, id: &str) -> Result<Option<String>> {
+    let casts = self
+      .cache
+      .get::<HashMap<String, String>>(key)
+      .await?
+      .unwrap_or_default();
+
+    Ok(casts.get(id).cloned())
+  }
+
+  /// Finds the idea cast in the Prop Lot channels, along with the Farcaster
+  /// channel it was cast in.
+  async fn get_idea_cast(&self, idea: &Idea) -> Result<Option<(String, String)>> {
+    for channel in &self.channels {
+      let key = channel.cache_key("ideas:casts");
+      if let Some(cast_hash) = self.get_cast_hash(&key, &idea.id.to_string()).await? {
+        let channel_key = channel.warp_cast_channel.clone().unwrap_or_default();
+        return Ok(Some((channel_key, cast_hash)));
+      }
+    }
+
+    Ok(None)
+  }
+
+  /// Farcaster channel of the default Prop Lot channel.
+  fn default_channel_key(&self) -> String {
+    self
+      .channels
+      .iter()
+      .find(|channel| channel.tags.is_empty())
+      .and_then(|channel| channel.warp_cast_channel.clone())
+      .unwrap_or_default()
+  }
+}
+
+#[async_trait(? Send)]
+impl Handler for FarcasterHandler {
+  async fn handle_idea_link(&self, idea: &Idea, proposal: &Proposal) -> Result<()> {
+    info!("Handling idea link: {}", proposal.key);
+
+    let idea_url = format!("{}/idea/{}", self.base_url, idea.id);
+    let (idea_channel_key, idea_cast) = match self.get_idea_cast(idea).await? {
+      Some((channel_key, cast_hash)) => (channel_key, Some(cast_hash)),
+      None => (self.default_channel_key(), None),
+    };
+    let proposal_cast = self
+      .get_cast_hash(&proposal.casts_key, &proposal.cast_id)
+      .await?;
+
+    // The idea thread links to the proposal, as a new cast when the idea was
+    // never cast, and the proposal thread links back to the idea.
+    let description = format!(
+      "Idea #{} “{}” is now {}: “{}”",
+      idea.id, idea.title, proposal.destination, proposal.title
+    );
+    self
+      .post_cast(
+        &self.bearer_token,
+        &idea_channel_key,
+        &description,
+        &proposal.url,
+        idea_cast,
+      )
+      .await?;
+
+    // Replies in the proposal thread are cast by the bot that owns it.
+    let source_token = self.source_tokens.get(&proposal.source);
+    if let (Some(proposal_cast), Some(source_token)) = (proposal_cast, source_token) {
+      let description = format!(
+        "This proposal started as Prop Lot idea #{}: “{}”",
+        idea.id, idea.title
+      );
+      self
+        .post_cast(
+          source_token,
+          &proposal.warp_cast_channel,
+          &description,
+          &idea_url,
+          Some(proposal_cast),
+        )
+        .await?;
+    }
+
+    Ok(())
+  }
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+use worker::Result;
+
+use crate::{linker::Proposal, prop_lot::Idea};
+
+pub(crate) mod discord;
+pub(crate) mod farcaster;
+
+#[async_trait(? Send)]
+pub trait Handler {
+  async fn handle_idea_link(&self, idea: &Idea, proposal: &Proposal) -> Result<()>;
+}
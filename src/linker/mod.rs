@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+
+use handler::{discord::DiscordHandler, farcaster::FarcasterHandler};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use worker::{Env, Result};
+
+use crate::{
+  cache::Cache,
+  lil_nouns,
+  linker::handler::Handler,
+  prop_house,
+  prop_house::{Auction, Community},
+  prop_lot::Idea,
+  utils::{get_slug, text::title_similarity},
+};
+
+mod handler;
+
+/// The bot a proposal comes from, which owns its Farcaster thread.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Source {
+  LilNouns,
+  PropHouse,
+}
+
+impl Source {
+  pub(crate) const ALL: [Source; 2] = [Source::LilNouns, Source::PropHouse];
+
+  /// Name of the secret holding the token the source casts with.
+  pub(crate) fn warp_cast_token_secret(&self) -> &'static str {
+    match self {
+      Source::LilNouns => "LIL_NOUNS_WARP_CAST_TOKEN",
+      Source::PropHouse => "PROP_HOUSE_WARP_CAST_TOKEN",
+    }
+  }
+}
+
+/// A proposal from Lil Nouns or Prop House that an idea may have become.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Proposal {
+  /// Identifies the proposal across sources, such as `lil_nouns:120`.
+  pub key: String,
+  pub title: String,
+  pub proposer: String,
+  /// Unix timestamp, zero when unknown.
+  pub created: i64,
+  pub url: String,
+  /// Where the idea went, such as "on-chain as Proposal #120".
+  pub destination: String,
+  pub source: Source,
+  /// Cache key of the source cast hashes, to reply in the proposal thread.
+  pub casts_key: String,
+  /// Farcaster channel the source casts the proposal in.
+  pub warp_cast_channel: String,
+  pub cast_id: String,
+}
+
+impl Proposal {
+  /// Tells whether the proposal grew out of the idea: created after it, and
+  /// either linked from the idea description or submitted by the idea creator
+  /// under a similar title.
+  fn follows(&self, idea: &Idea, min_similarity: f64) -> bool {
+    if idea.created == 0 || self.created <= idea.created {
+      return false;
+    }
+
+    let linked = idea
+      .description
+      .match_indices(&self.url)
+      .any(|(index, url)| {
+        !idea.description[index + url.len()..].starts_with(|c: char| c.is_ascii_alphanumeric())
+      });
+
+    linked
+      || (self.proposer.eq_ignore_ascii_case(&idea.creator_id)
+        && title_similarity(&self.title, &idea.title) > min_similarity)
+  }
+}
+
+pub struct Linker {
+  cache: Cache,
+  handlers: Vec<Box<dyn Handler>>,
+  lil_nouns_base_url: String,
+  lil_nouns_warp_cast_channel: String,
+  communities: Vec<Community>,
+  min_similarity: f64,
+}
+
+impl Linker {
+  pub fn new(
+    cache: Cache,
+    handlers: Vec<Box<dyn Handler>>,
+    lil_nouns_base_url: String,
+    lil_nouns_warp_cast_channel: String,
+    communities: Vec<Community>,
+    min_similarity: f64,
+  ) -> Self {
+    Self {
+      cache,
+      handlers,
+      lil_nouns_base_url,
+      lil_nouns_warp_cast_channel,
+      communities,
+      min_similarity,
+    }
+  }
+
+  pub async fn new_from_env(env: &Env) -> Result<Self> {
+    let cache = Cache::new_from_env(env);
+    let mut handlers = vec![];
+
+    if env.var("LINKER_DISCORD_ENABLED").unwrap().to_string() == "true" {
+      let discord_handler: Box<dyn Handler> = Box::new(DiscordHandler::new_from_env(env)?);
+      handlers.push(discord_handler);
+    }
+
+    if env.var("LINKER_FARCASTER_ENABLED").unwrap().to_string() == "true" {
+      let farcaster_handler: Box<dyn Handler> = Box::new(FarcasterHandler::new_from_env(env)?);
+      handlers.push(farcaster_handler);
+    }
+
+    let lil_nouns_base_url = env.var("LIL_NOUNS_BASE_URL")?.to_string();
+    let lil_nouns_warp_cast_channel = env.var("LIL_NOUNS_WARP_CAST_CHANNEL")?.to_string();
+    let communities = Community::list_from_env(env).await?;
+
+    let min_similarity = env
+      .var("LINKER_TITLE_SIMILARITY")
+      .ok()
+      .and_then(|value| value.to_string().parse::<f64>().ok())
+      .unwrap_or(0.75);
+
+    Ok(Self::new(
+      cache,
+      handlers,
+      lil_nouns_base_url,
+      lil_nouns_warp_cast_channel,
+      communities,
+      min_similarity,
+    ))
+  }
+
+  /// Collects the proposals the other bots keep in cache.
+  async fn proposals(&self) -> Result<Vec<Proposal>> {
+    let mut proposals = Vec::new();
+
+    if let Some(lil_nouns_proposals) = self
+      .cache
+      .get::<Vec<lil_nouns::Proposal>>("lil_nouns:proposals")
+      .await?
+    {
+      proposals.extend(lil_nouns_proposals.iter().map(|proposal| Proposal {
+        key: format!("lil_nouns:{}", proposal.id),
+        title: proposal.title.clone(),
+        proposer: proposal.proposer.clone(),
+        created: proposal.created,
+        url: format!("{}/{}", self.lil_nouns_base_url, proposal.id),
+        destination: format!("on-chain as Proposal #{}", proposal.id),
+        source: Source::LilNouns,
+        casts_key: "lil_nouns:proposals:casts".to_string(),
+        warp_cast_channel: self.lil_nouns_warp_cast_channel.clone(),
+        cast_id: proposal.id.to_string(),
+      }));
+    }
+
+    for community in &self.communities {
+      let auctions = self
+        .cache
+        .get::<Vec<Auction>>(&community.cache_key("auctions"))
+        .await?
+        .unwrap_or_default();
+      let Some(prop_house_proposals) = self
+        .cache
+        .get::<Vec<prop_house::Proposal>>(&community.cache_key("proposals"))
+        .await?
+      else {
+        continue;
+      };
+
+      for proposal in prop_house_proposals {
+        let Some(auction) = auctions.iter().find(|a| a.id == proposal.auction_id) else {
+          continue;
+        };
+
+        proposals.push(Proposal {
+          key: format!("prop_house:{}", proposal.id),
+          url: format!(
+            "{}/{}/{}",
+            community.base_url,
+            get_slug(&auction.title),
+            proposal.id
+          ),
+          destination: format!("on {} as proposal #{}", community.label(), proposal.id),
+          title: proposal.title,
+          proposer: proposal.address,
+          created: proposal.created,
+          source: Source::PropHouse,
+          casts_key: community.cache_key("proposals:casts"),
+          warp_cast_channel: community.warp_cast_channel.clone(),
+          cast_id: proposal.id.to_string(),
+        });
+      }
+    }
+
+    Ok(proposals)
+  }
+
+  pub async fn start(&self) -> Result<()> {
+    debug!("Start function started.");
+
+    let Some(ideas) = self.cache.get::<Vec<Idea>>("prop_lot:ideas").await? else {
+      warn!("No Prop Lot ideas in cache");
+      return Ok(());
+    };
+
+    let proposals = self.proposals().await?;
+    debug!("Found {:?} proposals to link.", proposals.len());
+
+    let cached_links = self
+      .cache
+      .get::<HashMap<String, Vec<String>>>("linker:links")
+      .await?;
+    // Links between existing ideas and proposals are only recorded.
+    let backfill = cached_links.is_none();
+    let mut links = cached_links.unwrap_or_default();
+    let mut updated = false;
+
+    for idea in ideas.iter().filter(|idea| !idea.deleted) {
+      for proposal in &proposals {
+        let linked = links
+          .get(&idea.id.to_string())
+          .is_some_and(|linked| linked.contains(&proposal.key));
+        if linked || !proposal.follows(idea, self.min_similarity) {
+          continue;
+        }
+
+        links
+          .entry(idea.id.to_string())
+          .or_default()
+          .push(proposal.key.clone());
+        updated = true;
+
+        if backfill {
+          continue;
+        }
+
+        info!("Handling a new idea link... ({:?})", proposal.key);
+        for handler in &self.handlers {
+          if let Err(err) = handler.handle_idea_link(idea, proposal).await {
+            error!("Failed to handle idea link: {:?}", err);
+          } else {
+            debug!("Successfully handled idea link: {:?}", idea.id);
+          }
+        }
+      }
+    }
+
+    if updated || backfill {
+      self.cache.put("linker:links", &links).await;
+      info!("Updated idea links in cache");
+    }
+
+    debug!("Start function finished.");
+
+    Ok(())
+  }
+}
@@ -1,12 +1,14 @@
-use chrono::{Duration, Utc};
 use graphql_client::{reqwest::post_graphql, GraphQLQuery};
 use log::{debug, error};
 use reqwest::Client;
 use worker::{Env, Result};
 
-use crate::meta_gov::{Proposal, Vote};
+use crate::meta_gov::{Choice, DelegateVote, Proposal, ProposalResult, Space, Vote};
 
-type Any = i32;
+type Any = serde_json::Value;
+
+const VOTES_PAGE_SIZE: i64 = 1000;
+const VOTES_MAX_PAGES: i64 = 5;
 
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -18,6 +20,16 @@ type Any = i32;
 )]
 struct ProposalQuery;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+  schema_path = "graphql/schemas/snapshot_schema.graphql",
+  query_path = "graphql/queries/snapshot_query.graphql",
+  response_derives = "Clone, Debug",
+  skip_serializing_none,
+  deprecated = "warn"
+)]
+struct ProposalResultQuery;
+
 #[derive(GraphQLQuery)]
 #[graphql(
   schema_path = "graphql/schemas/snapshot_schema.graphql",
@@ -28,28 +40,42 @@ struct ProposalQuery;
 )]
 struct VoteQuery;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+  schema_path = "graphql/schemas/lil_nouns_schema.graphql",
+  query_path = "graphql/queries/nouns_query.graphql",
+  response_derives = "Clone, Debug",
+  skip_serializing_none,
+  deprecated = "warn"
+)]
+struct DelegateVoteQuery;
+
 pub struct GraphQLFetcher {
   graphql_url: String,
+  nouns_graphql_url: String,
   space_id: String,
 }
 
 impl GraphQLFetcher {
-  pub fn new(graphql_url: String, space_id: String) -> Self {
+  pub fn new(graphql_url: String, nouns_graphql_url: String, space_id: String) -> Self {
     Self {
       graphql_url,
+      nouns_graphql_url,
       space_id,
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<GraphQLFetcher> {
+  pub fn new_from_env(env: &Env, space: &Space) -> Result<GraphQLFetcher> {
     let graphql_url = env.var("META_GOV_SNAPSHOT_GRAPHQL_URL")?.to_string();
-    let space_id = env.var("META_GOV_SNAPSHOT_SPACE_ID")?.to_string();
+    let nouns_graphql_url = env.var("META_GOV_NOUNS_GRAPHQL_URL")?.to_string();
+    let space_id = space.id.clone();
 
-    Ok(Self::new(graphql_url, space_id))
+    Ok(Self::new(graphql_url, nouns_graphql_url, space_id))
   }
 
   async fn fetch<QueryType: GraphQLQuery>(
     &self,
+    graphql_url: &str,
     variables: <QueryType as GraphQLQuery>::Variables,
   ) -> Option<<QueryType as GraphQLQuery>::ResponseData> {
     let client = Client::builder()
@@ -60,7 +86,7 @@ impl GraphQLFetcher {
       })
       .ok()?;
 
-    post_graphql::<QueryType, _>(&client, &self.graphql_url, variables)
+    post_graphql::<QueryType, _>(&client, graphql_url, variables)
       .await
       .map_err(|e| {
         error!("Failed to execute GraphQL request: {}", e);
@@ -75,7 +101,9 @@ impl GraphQLFetcher {
       space: Some(self.space_id.clone()),
     };
 
-    let response = self.fetch::<ProposalQuery>(variables).await?;
+    let response = self
+      .fetch::<ProposalQuery>(&self.graphql_url, variables)
+      .await?;
 
     let proposals = response
       .proposals
@@ -86,36 +114,138 @@ impl GraphQLFetcher {
         id: proposal.id.to_string(),
         title: proposal.title.to_string(),
         body: proposal.body.clone().unwrap(),
+        kind: proposal.type_.clone().unwrap_or_default(),
+        choices: proposal.choices.iter().flatten().cloned().collect(),
+        end: proposal.end,
       })
       .collect();
 
     Some(proposals)
   }
 
-  pub async fn fetch_votes(&self) -> Option<Vec<Vote>> {
-    let now = Utc::now();
-    let thirty_days_ago = now - Duration::days(30);
-
-    let variables = vote_query::Variables {
-      space: Some(self.space_id.clone()),
-      created_gt: thirty_days_ago.timestamp().try_into().unwrap(),
+  pub async fn fetch_results(&self, ids: &[String]) -> Option<Vec<ProposalResult>> {
+    let variables = proposal_result_query::Variables {
+      ids: Some(ids.iter().cloned().map(Some).collect()),
     };
 
-    let response = self.fetch::<VoteQuery>(variables).await?;
+    let response = self
+      .fetch::<ProposalResultQuery>(&self.graphql_url, variables)
+      .await?;
 
-    let votes = response
-      .votes
+    let results = response
+      .proposals
       .as_ref()?
       .iter()
-      .filter_map(|vote_option| vote_option.as_ref())
-      .map(|vote| Vote {
-        id: vote.id.to_string(),
-        voter: vote.voter.to_string(),
-        choice: vote.choice.try_into().unwrap(),
-        proposal_id: vote.proposal.clone().unwrap().id,
+      .filter_map(|proposal| proposal.as_ref())
+      .map(|proposal| ProposalResult {
+        proposal_id: proposal.id.to_string(),
+        state: proposal.state.clone().unwrap_or_default(),
+        choices: proposal.choices.iter().flatten().cloned().collect(),
+        scores: proposal
+          .scores
+          .iter()
+          .flatten()
+          .map(|score| score.unwrap_or_default())
+          .collect(),
+        scores_state: proposal.scores_state.clone().unwrap_or_default(),
+        scores_total: proposal.scores_total.unwrap_or_default(),
+        quorum: proposal.quorum,
+        votes: proposal.votes.unwrap_or_default(),
       })
       .collect();
 
+    Some(results)
+  }
+
+  /// Fetches the votes created at or after `created_gte`, oldest first, paging
+  /// through up to `VOTES_MAX_PAGES` pages.
+  pub async fn fetch_votes(&self, created_gte: i64) -> Option<Vec<Vote>> {
+    let mut votes = Vec::new();
+
+    for page in 0..VOTES_MAX_PAGES {
+      let variables = vote_query::Variables {
+        space: Some(self.space_id.clone()),
+        created_gte: Some(created_gte),
+        first: Some(VOTES_PAGE_SIZE),
+        skip: Some(page * VOTES_PAGE_SIZE),
+      };
+
+      let response = self
+        .fetch::<VoteQuery>(&self.graphql_url, variables)
+        .await?;
+      let page_votes = response.votes.as_ref()?;
+
+      votes.extend(
+        page_votes
+          .iter()
+          .filter_map(|vote_option| vote_option.as_ref())
+          .filter_map(|vote| {
+            let choice = serde_json::from_value::<Choice>(vote.choice.clone())
+              .map_err(|e| error!("Failed to parse vote choice ({}): {}", vote.id, e))
+              .ok()?;
+
+            Some(Vote {
+              id: vote.id.to_string(),
+              voter: vote.voter.to_string(),
+              choice,
+              proposal_id: vote.proposal.clone().unwrap().id,
+              vp: vote.vp.unwrap_or_default(),
+              reason: vote.reason.clone().unwrap_or_default(),
+              created: vote.created,
+            })
+          }),
+      );
+
+      if (page_votes.len() as i64) < VOTES_PAGE_SIZE {
+        break;
+      }
+    }
+
     Some(votes)
   }
+
+  /// Fetches the delegate's vote on a Nouns DAO proposal, along with whether
+  /// the proposal is settled and can no longer receive votes.
+  pub async fn fetch_delegate_vote(
+    &self,
+    proposal_id: &str,
+    delegate: &str,
+  ) -> Option<(Option<DelegateVote>, bool)> {
+    let variables = delegate_vote_query::Variables {
+      id: proposal_id.to_string(),
+      proposal: proposal_id.to_string(),
+      voter: delegate.to_lowercase(),
+    };
+
+    let response = self
+      .fetch::<DelegateVoteQuery>(&self.nouns_graphql_url, variables)
+      .await?;
+
+    let settled = matches!(
+      response.proposal?.status,
+      delegate_vote_query::ProposalStatus::CANCELLED
+        | delegate_vote_query::ProposalStatus::VETOED
+        | delegate_vote_query::ProposalStatus::QUEUED
+        | delegate_vote_query::ProposalStatus::EXECUTED
+    );
+
+    let vote = match response.votes.first() {
+      Some(vote) => {
+        // An unreadable direction is skipped rather than reported as against.
+        let direction = vote
+          .support_detailed
+          .try_into()
+          .map_err(|e| error!("Failed to read delegate vote direction: {}", e))
+          .ok()?;
+
+        Some(DelegateVote {
+          direction,
+          reason: vote.reason.clone().unwrap_or_default(),
+        })
+      }
+      None => None,
+    };
+
+    Some((vote, settled))
+  }
 }
@@ -1,42 +1,57 @@
 use async_trait::async_trait;
 use chrono::Local;
 use log::{error, info};
-use regex::Regex;
 use reqwest::{header, Client};
 use serde_json::{json, Value};
 use worker::{Env, Error, Result};
 
 use crate::{
   cache::Cache,
-  meta_gov::{handler::Handler, Proposal, Vote},
-  utils::{ens::get_domain_name, get_explorer_address, get_short_address},
+  meta_gov::{
+    handler::Handler,
+    DelegateVote,
+    PendingDelegateVote,
+    Proposal,
+    ProposalResult,
+    Space,
+    Vote,
+  },
+  utils::{
+    ens::get_domain_name,
+    get_explorer_address,
+    get_short_address,
+    text::{describe_duration, truncate_chars},
+  },
 };
 
 pub struct DiscordHandler {
-  pub base_url: String,
+  pub space: Space,
   pub webhook_url: String,
   pub cache: Cache,
   pub client: Client,
 }
 
 impl DiscordHandler {
-  pub fn new(base_url: String, webhook_url: String, cache: Cache, client: Client) -> Self {
+  pub fn new(space: Space, webhook_url: String, cache: Cache, client: Client) -> Self {
     Self {
-      base_url,
+      space,
       webhook_url,
       cache,
       client,
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<DiscordHandler> {
-    let base_url = env.var("META_GOV_BASE_URL")?.to_string();
-    let webhook_url = env.secret("META_GOV_DISCORD_WEBHOOK_URL")?.to_string();
+  pub fn new_from_env(env: &Env, space: &Space) -> Result<DiscordHandler> {
+    let webhook_secret = space
+      .discord_webhook_secret
+      .as_ref()
+      .ok_or("Discord webhook secret not configured")?;
+    let webhook_url = env.secret(webhook_secret)?.to_string();
 
     let cache = Cache::new_from_env(env);
     let client = Client::new();
 
-    Ok(Self::new(base_url, webhook_url, cache, client))
+    Ok(Self::new(space.clone(), webhook_url, cache, client))
   }
 
   async fn execute_webhook(&self, embed: Value) -> Result<()> {
@@ -60,57 +75,31 @@ impl DiscordHandler {
 
     Ok(())
   }
-
-  async fn extract_proposal_info(&self, proposal: Proposal) -> Result<(u32, String)> {
-    let captures = Regex::new(r"(\d+): (.+)")
-      .unwrap()
-      .captures(&*proposal.title)
-      .ok_or(Error::from("Capture Failed"))?;
-    let proposal_id = captures
-      .get(1)
-      .ok_or(Error::from("Failed to get proposal ID"))?;
-    let proposal_title = captures
-      .get(2)
-      .ok_or(Error::from("Failed to get proposal Title"))?;
-    let proposal_id = proposal_id
-      .as_str()
-      .parse::<u32>()
-      .map_err(|_| Error::from("Failed to parse proposal ID"))?;
-    let proposal_title = proposal_title.as_str().to_string();
-
-    Ok((proposal_id, proposal_title))
-  }
 }
 
 #[async_trait(? Send)]
 impl Handler for DiscordHandler {
   async fn handle_new_proposal(&self, proposal: &Proposal) -> Result<()> {
-    match self.extract_proposal_info(proposal.clone()).await {
-      Ok((proposal_id, proposal_title)) => {
-        info!("Handling new proposal: {}", proposal_title);
-
-        let url = format!("{}/{}", self.base_url, proposal_id);
-        let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
-        let description = format!(
-          "A new Meta Gov proposal has been created: “{}”",
-          proposal_title
-        );
-
-        let embed = json!({
-            "title": "New Meta Gov Proposal",
-            "description": description,
-            "url": url,
-            "color": 0xE40536,
-            "footer": {"text": date}
-        });
-
-        self.execute_webhook(embed).await?;
-      }
-      Err(e) => {
-        error!("Failed to extract proposal info: {}", e);
-      }
+    let info = self.space.proposal_info(proposal);
+
+    info!("Handling new proposal: {}", info.title);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let mut description = format!("A new Meta Gov proposal has been created: “{}”", info.title);
+    if let Some(excerpt) = &info.excerpt {
+      description = format!("{}\n\n{}", description, excerpt);
     }
 
+    let embed = json!({
+        "title": "New Meta Gov Proposal",
+        "description": description,
+        "url": info.url,
+        "color": 0xE40536,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
     Ok(())
   }
 
@@ -119,57 +108,146 @@ impl Handler for DiscordHandler {
 
     let proposals = self
       .cache
-      .get::<Vec<Proposal>>("meta_gov:proposals")
+      .get::<Vec<Proposal>>(&self.space.cache_key("proposals"))
       .await?
-      .unwrap();
+      .ok_or("Proposals not found in cache")?;
 
     let proposal = proposals
       .iter()
       .find(|&a| a.id == vote.proposal_id)
-      .unwrap()
-      .clone();
-
-    match self.extract_proposal_info(proposal.clone()).await {
-      Ok((proposal_id, proposal_title)) => {
-        info!("Handling new proposal: {}", proposal_title);
-
-        let url = format!("{}/{}", self.base_url, proposal_id);
-        let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
-        let wallet = get_domain_name(&vote.voter)
-          .await
-          .unwrap_or(get_short_address(&vote.voter));
-
-        let description = format!(
-          "{} has voted {} “{}” proposal.",
-          wallet,
-          match vote.choice {
-            1 => "for",
-            2 => "against",
-            3 => "abstain on",
-            _ => "unknown",
-          },
-          proposal_title
-        );
-        let explorer = get_explorer_address(&vote.voter);
-
-        let embed = json!({
-            "title": "New Meta Gov Proposal Vote",
-            "description": description,
-            "url": url,
-            "color": 0xE40536,
-            "footer": {"text": date},
-            "author": {
-                "name": wallet,
-                "url": explorer,
-            }
-        });
-
-        self.execute_webhook(embed).await?;
-      }
-      Err(e) => {
-        error!("Failed to extract proposal info: {}", e);
-      }
+      .cloned()
+      .ok_or("Proposal not found")?;
+
+    let info = self.space.proposal_info(&proposal);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let wallet = get_domain_name(&vote.voter)
+      .await
+      .unwrap_or(get_short_address(&vote.voter));
+
+    let mut description = format!(
+      "{} has {} “{}” proposal with {}.",
+      wallet,
+      vote.describe_choice(&proposal),
+      info.title,
+      vote.voting_power()
+    );
+    if !vote.reason.is_empty() {
+      let reason = truncate_chars(
+        &vote.reason,
+        320_usize.saturating_sub(10 + description.chars().count()),
+      );
+      description = format!("{}\n\n“{}”", description, reason);
     }
+    let explorer = get_explorer_address(&vote.voter);
+
+    let embed = json!({
+        "title": "New Meta Gov Proposal Vote",
+        "description": description,
+        "url": info.url,
+        "color": 0xE40536,
+        "footer": {"text": date},
+        "author": {
+            "name": wallet,
+            "url": explorer,
+        }
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_proposal_result(
+    &self,
+    proposal: &Proposal,
+    result: &ProposalResult,
+  ) -> Result<()> {
+    let info = self.space.proposal_info(proposal);
+
+    info!("Handling proposal result: {}", info.title);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let description = format!(
+      "Voting has closed on “{}” proposal.\n\n{}\n\n{}",
+      info.title,
+      result.summary(),
+      result.outcome()
+    );
+
+    let embed = json!({
+        "title": "Meta Gov Proposal Results",
+        "description": description,
+        "url": info.url,
+        "color": 0xE40536,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_proposal_reminder(
+    &self,
+    proposal: &Proposal,
+    result: &ProposalResult,
+    remaining: i64,
+  ) -> Result<()> {
+    let info = self.space.proposal_info(proposal);
+
+    info!("Handling proposal reminder: {}", info.title);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let description = format!(
+      "Voting on “{}” proposal closes in {}.\n\n{}",
+      info.title,
+      describe_duration(remaining),
+      result.standing()
+    );
+
+    let embed = json!({
+        "title": "Meta Gov Proposal Closing Soon",
+        "description": description,
+        "url": info.url,
+        "color": 0xE40536,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_delegate_vote(
+    &self,
+    pending: &PendingDelegateVote,
+    vote: &DelegateVote,
+  ) -> Result<()> {
+    let info = self.space.proposal_info(&pending.proposal);
+
+    info!("Handling delegate vote: {}", info.title);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let mut description = pending.describe(vote, &info.title);
+    if !vote.reason.is_empty() {
+      let reason = truncate_chars(
+        &vote.reason,
+        320_usize.saturating_sub(10 + description.chars().count()),
+      );
+      description = format!("{}\n\n“{}”", description, reason);
+    }
+
+    let embed = json!({
+        "title": "Meta Gov On-chain Vote",
+        "description": description,
+        "url": info.url,
+        "color": 0xE40536,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
     Ok(())
   }
 }
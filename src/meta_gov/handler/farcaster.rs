@@ -2,7 +2,6 @@ use std::collections::HashMap;
 
 use async_trait::async_trait;
 use log::{debug, error, info};
-use regex::Regex;
 use reqwest::{
   header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE},
   Client,
@@ -13,12 +12,24 @@ use worker::{Env, Error, Result};
 
 use crate::{
   cache::Cache,
-  meta_gov::{handler::Handler, Proposal, Vote},
-  utils::{ens::get_wallet_handle, link::Link},
+  meta_gov::{
+    handler::Handler,
+    DelegateVote,
+    PendingDelegateVote,
+    Proposal,
+    ProposalResult,
+    Space,
+    Vote,
+  },
+  utils::{
+    ens::get_wallet_handle,
+    link::Link,
+    text::{describe_duration, truncate_bytes},
+  },
 };
 
 pub(crate) struct FarcasterHandler {
-  base_url: String,
+  space: Space,
   warpcast_url: String,
   bearer_token: String,
   channel_key: String,
@@ -29,7 +40,7 @@ pub(crate) struct FarcasterHandler {
 
 impl FarcasterHandler {
   pub fn new(
-    base_url: String,
+    space: Space,
     warpcast_url: String,
     bearer_token: String,
     channel_key: String,
@@ -38,7 +49,7 @@ impl FarcasterHandler {
     link: Link,
   ) -> Self {
     Self {
-      base_url,
+      space,
       warpcast_url,
       bearer_token,
       channel_key,
@@ -48,18 +59,17 @@ impl FarcasterHandler {
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<FarcasterHandler> {
-    let base_url = env.var("META_GOV_BASE_URL")?.to_string();
+  pub fn new_from_env(env: &Env, space: &Space) -> Result<FarcasterHandler> {
     let warpcast_url = env.var("WARP_CAST_API_BASE_URL")?.to_string();
     let bearer_token = env.secret("META_GOV_WARP_CAST_TOKEN")?.to_string();
-    let channel_key = env.var("META_GOV_WARP_CAST_CHANNEL")?.to_string();
+    let channel_key = space.warp_cast_channel.clone();
 
     let cache = Cache::new_from_env(env);
     let client = Client::new();
     let link = Link::new_from_env(&env);
 
     Ok(Self::new(
-      base_url,
+      space.clone(),
       warpcast_url,
       bearer_token,
       channel_key,
@@ -98,42 +108,26 @@ impl FarcasterHandler {
 
     Ok(response)
   }
-
-  async fn extract_proposal_info(&self, proposal: Proposal) -> Result<(String, String)> {
-    let captures = Regex::new(r"(\d+): (.+)")
-      .unwrap()
-      .captures(&*proposal.title)
-      .ok_or(Error::from("Capture Failed"))?;
-    let proposal_id = captures
-      .get(1)
-      .ok_or(Error::from("Failed to get proposal ID"))?;
-    let proposal_title = captures
-      .get(2)
-      .ok_or(Error::from("Failed to get proposal Title"))?;
-    let proposal_id = proposal_id.as_str().to_string();
-    let proposal_title = proposal_title.as_str().to_string();
-
-    Ok((proposal_id, proposal_title))
-  }
 }
 
 #[async_trait(? Send)]
 impl Handler for FarcasterHandler {
   async fn handle_new_proposal(&self, proposal: &Proposal) -> Result<()> {
-    let (proposal_id, proposal_title) = self.extract_proposal_info(proposal.clone()).await?;
+    let info = self.space.proposal_info(proposal);
 
-    info!("Handling new proposal: {}", proposal_title);
+    info!("Handling new proposal: {}", info.title);
 
     let url = &self
       .link
-      .generate(format!("{}/{}", self.base_url, proposal_id))
+      .generate(info.url.clone())
       .await
-      .unwrap_or_else(|_| format!("{}/{}", self.base_url, proposal_id));
+      .unwrap_or_else(|_| info.url.clone());
 
-    let description = format!(
-      "A new Meta Gov proposal has been created: “{}”",
-      proposal_title
-    );
+    let mut description = format!("A new Meta Gov proposal has been created: “{}”", info.title);
+    if let Some(excerpt) = &info.excerpt {
+      let excerpt = truncate_bytes(excerpt, 320_usize.saturating_sub(10 + description.len()));
+      description = format!("{}\n\n{}", description, excerpt);
+    }
 
     let request_data = json!({
         "text": description,
@@ -167,15 +161,15 @@ impl Handler for FarcasterHandler {
 
     let mut proposals_casts = self
       .cache
-      .get::<HashMap<String, String>>("meta_gov:proposals:casts")
+      .get::<HashMap<String, String>>(&self.space.cache_key("proposals:casts"))
       .await?
       .unwrap_or_default();
 
-    proposals_casts.insert(proposal_id, cast_hash.to_string());
+    proposals_casts.insert(info.key, cast_hash.to_string());
 
     self
       .cache
-      .put("meta_gov:proposals:casts", &proposals_casts)
+      .put(&self.space.cache_key("proposals:casts"), &proposals_casts)
       .await;
 
     Ok(())
@@ -186,9 +180,9 @@ impl Handler for FarcasterHandler {
 
     let proposals = self
       .cache
-      .get::<Vec<Proposal>>("meta_gov:proposals")
+      .get::<Vec<Proposal>>(&self.space.cache_key("proposals"))
       .await?
-      .unwrap();
+      .ok_or("Proposals not found in cache")?;
 
     let proposal = proposals
       .iter()
@@ -196,32 +190,156 @@ impl Handler for FarcasterHandler {
       .clone()
       .ok_or("Proposal not found in the funding list.")?;
 
-    let (proposal_id, proposal_title) = self.extract_proposal_info(proposal.clone()).await?;
+    let info = self.space.proposal_info(proposal);
 
     let proposals_casts = self
       .cache
-      .get::<HashMap<String, String>>("meta_gov:proposals:casts")
+      .get::<HashMap<String, String>>(&self.space.cache_key("proposals:casts"))
       .await?
       .unwrap_or_default();
 
     let cast_hash = proposals_casts
-      .get(&proposal_id)
+      .get(&info.key)
       .ok_or("Cast hash not found")?;
 
     let wallet = get_wallet_handle(&vote.voter, "xyz.farcaster").await;
 
-    let description = format!(
-      "{} has voted {} “{}” proposal.",
+    let mut description = format!(
+      "{} has {} “{}” proposal with {}.",
       wallet,
-      match vote.choice {
-        1 => "for",
-        2 => "against",
-        3 => "abstain on",
-        _ => "unknown",
-      },
-      proposal_title
+      vote.describe_choice(proposal),
+      info.title,
+      vote.voting_power()
+    );
+    if !vote.reason.is_empty() {
+      let reason = truncate_bytes(
+        &vote.reason,
+        320_usize.saturating_sub(10 + description.len()),
+      );
+      description = format!("{}\n\n“{}”", description, reason);
+    }
+
+    let request_data = json!({
+      "text": description,
+      "channelKey": self.channel_key,
+      "parent": {"hash": cast_hash},
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_proposal_result(
+    &self,
+    proposal: &Proposal,
+    result: &ProposalResult,
+  ) -> Result<()> {
+    let info = self.space.proposal_info(proposal);
+
+    info!("Handling proposal result: {}", info.title);
+
+    let proposals_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.space.cache_key("proposals:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let description = truncate_bytes(
+      &format!(
+        "Voting has closed on “{}” proposal.\n\n{}\n\n{}",
+        info.title,
+        result.summary(),
+        result.outcome()
+      ),
+      320,
     );
 
+    let request_data = match proposals_casts.get(&info.key) {
+      Some(cast_hash) => json!({
+        "text": description,
+        "channelKey": self.channel_key,
+        "parent": {"hash": cast_hash},
+      }),
+      None => {
+        let url = &self
+          .link
+          .generate(info.url.clone())
+          .await
+          .unwrap_or_else(|_| info.url.clone());
+
+        json!({
+          "text": description,
+          "embeds": [url],
+          "channelKey": self.channel_key
+        })
+      }
+    };
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_proposal_reminder(
+    &self,
+    proposal: &Proposal,
+    result: &ProposalResult,
+    remaining: i64,
+  ) -> Result<()> {
+    let info = self.space.proposal_info(proposal);
+
+    info!("Handling proposal reminder: {}", info.title);
+
+    let proposals_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.space.cache_key("proposals:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let cast_hash = proposals_casts
+      .get(&info.key)
+      .ok_or("Cast hash not found")?;
+
+    let description = format!(
+      "Voting on “{}” proposal closes in {}.\n\n{}",
+      info.title,
+      describe_duration(remaining),
+      result.standing()
+    );
+
+    let request_data = json!({
+      "text": description,
+      "channelKey": self.channel_key,
+      "parent": {"hash": cast_hash},
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_delegate_vote(
+    &self,
+    pending: &PendingDelegateVote,
+    vote: &DelegateVote,
+  ) -> Result<()> {
+    let info = self.space.proposal_info(&pending.proposal);
+
+    info!("Handling delegate vote: {}", info.title);
+
+    let proposals_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.space.cache_key("proposals:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let cast_hash = proposals_casts
+      .get(&info.key)
+      .ok_or("Cast hash not found")?;
+
+    let description = pending.describe(vote, &info.title);
+
     let request_data = json!({
       "text": description,
       "channelKey": self.channel_key,
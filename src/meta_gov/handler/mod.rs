@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use worker::Result;
 
-use crate::meta_gov::{Proposal, Vote};
+use crate::meta_gov::{DelegateVote, PendingDelegateVote, Proposal, ProposalResult, Vote};
 
 pub(crate) mod discord;
 pub(crate) mod farcaster;
@@ -10,4 +10,20 @@ pub(crate) mod farcaster;
 pub trait Handler {
   async fn handle_new_proposal(&self, proposal: &Proposal) -> Result<()>;
   async fn handle_new_vote(&self, vote: &Vote) -> Result<()>;
+  async fn handle_proposal_result(
+    &self,
+    proposal: &Proposal,
+    result: &ProposalResult,
+  ) -> Result<()>;
+  async fn handle_proposal_reminder(
+    &self,
+    proposal: &Proposal,
+    result: &ProposalResult,
+    remaining: i64,
+  ) -> Result<()>;
+  async fn handle_delegate_vote(
+    &self,
+    pending: &PendingDelegateVote,
+    vote: &DelegateVote,
+  ) -> Result<()>;
 }
@@ -1,85 +1,542 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::Utc;
 use handler::{discord::DiscordHandler, farcaster::FarcasterHandler};
 use log::{debug, error, info, warn};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use worker::{Env, Result};
+use worker::{Env, Error, Result};
 
 use crate::{
   cache::Cache,
   meta_gov::{fetcher::GraphQLFetcher, handler::Handler},
+  utils::text::{strip_markdown, truncate_chars},
 };
 
 mod fetcher;
 mod handler;
 
+const DEFAULT_TITLE_PATTERN: &str = r"(\d+): (.+)";
+const PROPOSAL_EXCERPT_LENGTH: usize = 280;
+const DELEGATE_VOTE_WATCH_PERIOD: i64 = 14 * 24 * 60 * 60;
+
+/// A Snapshot space followed by Meta Gov, with its own sinks and cache
+/// namespace.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Space {
+  pub(crate) id: String,
+  pub(crate) base_url: String,
+  /// Patterns capturing the linked proposal id and title, as `id` and `title`
+  /// named groups or the first two groups.
+  #[serde(default = "Space::default_title_patterns")]
+  pub(crate) title_patterns: Vec<String>,
+  /// Name of the secret holding the space Discord webhook URL.
+  #[serde(default)]
+  pub(crate) discord_webhook_secret: Option<String>,
+  #[serde(default)]
+  pub(crate) warp_cast_channel: String,
+  #[serde(default)]
+  pub(crate) cache_namespace: Option<String>,
+  /// Address of the delegate casting the space outcome on linked Nouns DAO
+  /// proposals.
+  #[serde(default)]
+  pub(crate) nouns_delegate: Option<String>,
+}
+
+impl Space {
+  fn default_title_patterns() -> Vec<String> {
+    vec![DEFAULT_TITLE_PATTERN.to_string()]
+  }
+
+  /// Reads the spaces from `META_GOV_SPACES`, a JSON array, falling back to the
+  /// single space described by the `META_GOV_*` variables.
+  pub fn list_from_env(env: &Env) -> Result<Vec<Space>> {
+    if let Ok(spaces) = env.var("META_GOV_SPACES") {
+      return serde_json::from_str(&spaces.to_string())
+        .map_err(|e| Error::from(format!("Failed to parse META_GOV_SPACES: {}", e)));
+    }
+
+    Ok(vec![Space {
+      id: env.var("META_GOV_SNAPSHOT_SPACE_ID")?.to_string(),
+      base_url: env.var("META_GOV_BASE_URL")?.to_string(),
+      title_patterns: Self::default_title_patterns(),
+      discord_webhook_secret: Some("META_GOV_DISCORD_WEBHOOK_URL".to_string()),
+      warp_cast_channel: env.var("META_GOV_WARP_CAST_CHANNEL")?.to_string(),
+      cache_namespace: Some("meta_gov".to_string()),
+      nouns_delegate: env
+        .var("META_GOV_NOUNS_DELEGATE_ADDRESS")
+        .map(|address| address.to_string())
+        .ok()
+        .filter(|address| !address.is_empty()),
+    }])
+  }
+
+  /// Resolves how a proposal is presented, falling back to its Snapshot page
+  /// and body excerpt when the title doesn't match any pattern.
+  pub(crate) fn proposal_info(&self, proposal: &Proposal) -> ProposalInfo {
+    for pattern in &self.title_patterns {
+      let regex = match Regex::new(pattern) {
+        Ok(regex) => regex,
+        Err(e) => {
+          error!("Invalid title pattern {:?}: {}", pattern, e);
+          continue;
+        }
+      };
+
+      let Some(captures) = regex.captures(&proposal.title) else {
+        continue;
+      };
+
+      let id = captures.name("id").or_else(|| captures.get(1));
+      let title = captures.name("title").or_else(|| captures.get(2));
+
+      if let (Some(id), Some(title)) = (id, title) {
+        return ProposalInfo {
+          key: id.as_str().to_string(),
+          title: title.as_str().to_string(),
+          url: format!("{}/{}", self.base_url, id.as_str()),
+          excerpt: None,
+        };
+      }
+    }
+
+    debug!("No title pattern matched proposal: {:?}", proposal.id);
+
+    ProposalInfo {
+      key: proposal.id.clone(),
+      title: proposal.title.clone(),
+      url: format!(
+        "https://snapshot.org/#/{}/proposal/{}",
+        self.id, proposal.id
+      ),
+      excerpt: Some(proposal.excerpt(PROPOSAL_EXCERPT_LENGTH)),
+    }
+  }
+
+  pub(crate) fn cache_key(&self, key: &str) -> String {
+    match &self.cache_namespace {
+      Some(namespace) => format!("{}:{}", namespace, key),
+      None => format!("meta_gov:{}:{}", self.id, key),
+    }
+  }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Proposal {
   pub(crate) id: String,
   pub(crate) title: String,
   body: String,
+  #[serde(default)]
+  pub(crate) kind: String,
+  #[serde(default)]
+  pub(crate) choices: Vec<String>,
+  #[serde(default)]
+  pub(crate) end: i64,
+}
+
+/// How a proposal is presented in announcements.
+pub(crate) struct ProposalInfo {
+  /// Key linking the proposal to its announcements, e.g. its Farcaster cast.
+  pub(crate) key: String,
+  pub(crate) title: String,
+  pub(crate) url: String,
+  /// Body excerpt, set when the title doesn't match any of the space patterns.
+  pub(crate) excerpt: Option<String>,
+}
+
+impl Proposal {
+  /// Returns the body as plain text, cut to `limit` characters.
+  pub(crate) fn excerpt(&self, limit: usize) -> String {
+    truncate_chars(&strip_markdown(&self.body), limit)
+  }
+
+  /// Returns the label of a 1-based Snapshot choice index.
+  pub(crate) fn choice_label(&self, index: usize) -> String {
+    index
+      .checked_sub(1)
+      .and_then(|i| self.choices.get(i))
+      .cloned()
+      .unwrap_or_else(|| format!("Choice {}", index))
+  }
+}
+
+/// The outcome of a Snapshot proposal once voting has closed.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProposalResult {
+  pub(crate) proposal_id: String,
+  pub(crate) state: String,
+  pub(crate) choices: Vec<String>,
+  pub(crate) scores: Vec<f64>,
+  pub(crate) scores_state: String,
+  pub(crate) scores_total: f64,
+  pub(crate) quorum: f64,
+  #[serde(default)]
+  pub(crate) votes: i64,
+}
+
+impl ProposalResult {
+  /// Whether the proposal is closed and its scores will no longer change.
+  pub(crate) fn is_final(&self) -> bool {
+    self.state == "closed" && self.scores_state == "final"
+  }
+
+  pub(crate) fn quorum_reached(&self) -> bool {
+    self.scores_total >= self.quorum
+  }
+
+  /// Returns the label of the choice with the highest score, if any votes were
+  /// cast.
+  pub(crate) fn winner(&self) -> Option<String> {
+    if self.scores_total <= 0.0 {
+      return None;
+    }
+
+    self
+      .scores
+      .iter()
+      .enumerate()
+      .max_by(|(_, a), (_, b)| a.total_cmp(b))
+      .and_then(|(index, _)| self.choices.get(index))
+      .cloned()
+  }
+
+  /// Describes the winning choice, taking quorum into account.
+  pub(crate) fn outcome(&self) -> String {
+    match self.winner() {
+      Some(winner) if self.quorum_reached() => format!("Winning choice: ‘{}’", winner),
+      Some(winner) => format!("‘{}’ led, but quorum was not reached.", winner),
+      None => "No votes were cast.".to_string(),
+    }
+  }
+
+  /// Describes the current leader, participation against quorum and voter
+  /// count.
+  pub(crate) fn standing(&self) -> String {
+    let leader = match self.winner() {
+      Some(winner) => {
+        let score = self
+          .choices
+          .iter()
+          .position(|choice| *choice == winner)
+          .and_then(|index| self.scores.get(index))
+          .copied()
+          .unwrap_or_default();
+        format!(
+          "Leading: ‘{}’ ({:.2}%)",
+          winner,
+          score / self.scores_total * 100.0
+        )
+      }
+      None => "Leading: no votes yet".to_string(),
+    };
+
+    let participation = if self.quorum > 0.0 {
+      format!(
+        "Participation: {:.0} of {:.0} quorum ({:.2}%)",
+        self.scores_total,
+        self.quorum,
+        self.scores_total / self.quorum * 100.0
+      )
+    } else {
+      format!("Participation: {:.0}", self.scores_total)
+    };
+
+    format!("{}\n{}\nVoters: {}", leader, participation, self.votes)
+  }
+
+  /// Formats each choice with its share of the total score, one per line.
+  pub(crate) fn summary(&self) -> String {
+    self
+      .choices
+      .iter()
+      .zip(self.scores.iter())
+      .map(|(choice, score)| {
+        let percentage = if self.scores_total > 0.0 {
+          score / self.scores_total * 100.0
+        } else {
+          0.0
+        };
+        format!("{}: {:.2}%", choice, percentage)
+      })
+      .collect::<Vec<_>>()
+      .join("\n")
+  }
+}
+
+/// The delegate's on-chain vote on a Nouns DAO proposal.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DelegateVote {
+  pub(crate) direction: usize,
+  pub(crate) reason: String,
+}
+
+impl DelegateVote {
+  pub(crate) fn label(&self) -> &'static str {
+    match self.direction {
+      0 => "against",
+      1 => "for",
+      2 => "abstain",
+      _ => "unknown",
+    }
+  }
+}
+
+/// A closed Snapshot proposal awaiting the delegate's vote on its linked Nouns
+/// DAO proposal.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PendingDelegateVote {
+  pub(crate) proposal: Proposal,
+  pub(crate) nouns_proposal_id: String,
+  /// The winning Snapshot choice, if quorum was reached.
+  pub(crate) outcome: Option<String>,
+  pub(crate) closed: i64,
+}
+
+impl PendingDelegateVote {
+  /// Describes the on-chain vote and whether it follows the Snapshot outcome.
+  pub(crate) fn describe(&self, vote: &DelegateVote, title: &str) -> String {
+    let verdict = match &self.outcome {
+      Some(outcome) if outcome.eq_ignore_ascii_case(vote.label()) => {
+        format!("matching the Meta Gov outcome (‘{}’)", outcome)
+      }
+      Some(outcome) => format!("differing from the Meta Gov outcome (‘{}’)", outcome),
+      None => "with no Meta Gov outcome to follow".to_string(),
+    };
+
+    format!(
+      "Lil Nouns voted {} on Nouns DAO proposal {} “{}”, {}.",
+      vote.label(),
+      self.nouns_proposal_id,
+      title,
+      verdict
+    )
+  }
+}
+
+/// A Snapshot vote choice, whose shape depends on the proposal voting system.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Choice {
+  /// `basic` and `single-choice` votes.
+  Single(usize),
+  /// `approval` and `ranked-choice` votes.
+  Multiple(Vec<usize>),
+  /// `weighted` and `quadratic` votes, keyed by choice index.
+  Weighted(BTreeMap<String, f64>),
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Vote {
   pub(crate) id: String,
   pub(crate) voter: String,
-  pub(crate) choice: isize,
+  pub(crate) choice: Choice,
   pub(crate) proposal_id: String,
+  #[serde(default)]
+  pub(crate) vp: f64,
+  #[serde(default)]
+  pub(crate) reason: String,
+  #[serde(default)]
+  pub(crate) created: i64,
+}
+
+/// Position of the last seen Snapshot vote, so each run only fetches newer
+/// votes.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VoteCursor {
+  pub(crate) created: i64,
+  /// Ids of the votes already seen at `created`, as several votes can share a
+  /// timestamp.
+  pub(crate) ids: Vec<String>,
+}
+
+impl VoteCursor {
+  pub(crate) fn advance(&mut self, votes: &[Vote]) {
+    for vote in votes {
+      if vote.created > self.created {
+        self.created = vote.created;
+        self.ids.clear();
+      }
+
+      if vote.created == self.created && !self.ids.contains(&vote.id) {
+        self.ids.push(vote.id.clone());
+      }
+    }
+  }
+}
+
+impl Vote {
+  /// Formats the voting power, e.g. "1 vote" or "12.5 votes".
+  pub(crate) fn voting_power(&self) -> String {
+    let amount = if self.vp.fract() == 0.0 {
+      format!("{:.0}", self.vp)
+    } else {
+      format!("{:.2}", self.vp)
+    };
+
+    if self.vp == 1.0 {
+      format!("{} vote", amount)
+    } else {
+      format!("{} votes", amount)
+    }
+  }
+
+  /// Describes the vote choice, e.g. "voted for ‘Option B’ on".
+  pub(crate) fn describe_choice(&self, proposal: &Proposal) -> String {
+    match (&self.choice, proposal.kind.as_str()) {
+      (Choice::Single(choice), kind) => {
+        let label = proposal.choice_label(*choice);
+        match label.to_lowercase().as_str() {
+          "abstain" => "abstained on".to_string(),
+          "for" | "against" if matches!(kind, "basic" | "") => {
+            format!("voted {}", label.to_lowercase())
+          }
+          _ => format!("voted for ‘{}’ on", label),
+        }
+      }
+      (Choice::Multiple(choices), "ranked-choice") => format!(
+        "ranked {} on",
+        choices
+          .iter()
+          .map(|&choice| proposal.choice_label(choice))
+          .collect::<Vec<_>>()
+          .join(" > ")
+      ),
+      (Choice::Multiple(choices), _) => format!(
+        "approved {} on",
+        choices
+          .iter()
+          .map(|&choice| proposal.choice_label(choice))
+          .collect::<Vec<_>>()
+          .join(", ")
+      ),
+      (Choice::Weighted(weights), _) => {
+        let total: f64 = weights.values().sum();
+        if total <= 0.0 {
+          return "voted on".to_string();
+        }
+
+        let mut weights: Vec<_> = weights
+          .iter()
+          .filter_map(|(choice, weight)| Some((choice.parse::<usize>().ok()?, *weight)))
+          .filter(|(_, weight)| *weight > 0.0)
+          .collect();
+        weights.sort_by_key(|(choice, _)| *choice);
+
+        format!(
+          "split their vote {} on",
+          weights
+            .iter()
+            .map(|(choice, weight)| format!(
+              "{:.0}% {}",
+              weight / total * 100.0,
+              proposal.choice_label(*choice)
+            ))
+            .collect::<Vec<_>>()
+            .join(", ")
+        )
+      }
+    }
+  }
 }
 
 pub struct MetaGov {
+  space: Space,
   cache: Cache,
   fetcher: GraphQLFetcher,
   handlers: Vec<Box<dyn Handler>>,
+  min_vp: f64,
+  reminder_hours: Vec<i64>,
 }
 
 impl MetaGov {
-  pub fn new(cache: Cache, fetcher: GraphQLFetcher, handlers: Vec<Box<dyn Handler>>) -> Self {
+  pub fn new(
+    space: Space,
+    cache: Cache,
+    fetcher: GraphQLFetcher,
+    handlers: Vec<Box<dyn Handler>>,
+    min_vp: f64,
+    reminder_hours: Vec<i64>,
+  ) -> Self {
     Self {
+      space,
       cache,
       fetcher,
       handlers,
+      min_vp,
+      reminder_hours,
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<Self> {
+  pub fn new_from_env(env: &Env, space: Space) -> Result<Self> {
     let cache = Cache::new_from_env(env);
-    let fetcher = GraphQLFetcher::new_from_env(env)?;
+    let fetcher = GraphQLFetcher::new_from_env(env, &space)?;
     let mut handlers = vec![];
 
-    if env.var("META_GOV_DISCORD_ENABLED").unwrap().to_string() == "true" {
-      let discord_handler: Box<dyn Handler> = Box::new(DiscordHandler::new_from_env(env)?);
+    if env.var("META_GOV_DISCORD_ENABLED").unwrap().to_string() == "true"
+      && space.discord_webhook_secret.is_some()
+    {
+      let discord_handler: Box<dyn Handler> = Box::new(DiscordHandler::new_from_env(env, &space)?);
       handlers.push(discord_handler);
     }
 
     if env.var("META_GOV_FARCASTER_ENABLED").unwrap().to_string() == "true" {
-      let farcaster_handler: Box<dyn Handler> = Box::new(FarcasterHandler::new_from_env(env)?);
+      let farcaster_handler: Box<dyn Handler> =
+        Box::new(FarcasterHandler::new_from_env(env, &space)?);
       handlers.push(farcaster_handler);
     }
 
-    Ok(Self::new(cache, fetcher, handlers))
+    let min_vp = env
+      .var("META_GOV_VOTE_MIN_VP")
+      .map(|value| value.to_string().parse::<f64>().unwrap_or_default())
+      .unwrap_or_default();
+
+    let reminder_hours = env
+      .var("META_GOV_REMINDER_HOURS")
+      .map(|value| {
+        value
+          .to_string()
+          .split(',')
+          .filter_map(|hours| hours.trim().parse::<i64>().ok())
+          .collect()
+      })
+      .unwrap_or_default();
+
+    Ok(Self::new(
+      space,
+      cache,
+      fetcher,
+      handlers,
+      min_vp,
+      reminder_hours,
+    ))
   }
 
   pub async fn setup(&self) {
     debug!("Setup function started.");
 
-    if !self.cache.has("meta_gov:proposals").await {
+    if !self.cache.has(&self.space.cache_key("proposals")).await {
       if let Some(proposals) = self.fetcher.fetch_proposals().await {
         info!("Fetched {:?} proposals.", proposals.len());
         debug!("Putting fetched proposals into cache.");
-        self.cache.put("meta_gov:proposals", &proposals).await;
+        self
+          .cache
+          .put(&self.space.cache_key("proposals"), &proposals)
+          .await;
       } else {
         warn!("Failed to fetch proposals");
       }
     };
 
-    if !self.cache.has("meta_gov:votes").await {
-      if let Some(votes) = self.fetcher.fetch_votes().await {
-        info!("Fetched {:?} votes.", votes.len());
-        debug!("Putting fetched votes into cache.");
-        self.cache.put("meta_gov:votes", &votes).await;
-      } else {
-        warn!("Failed to fetch votes");
-      }
+    if !self.cache.has(&self.space.cache_key("votes:cursor")).await {
+      let cursor = VoteCursor {
+        created: Utc::now().timestamp(),
+        ids: Vec::new(),
+      };
+      debug!("Putting initial vote cursor into cache.");
+      self
+        .cache
+        .put(&self.space.cache_key("votes:cursor"), &cursor)
+        .await;
     };
 
     debug!("Setup function finished.");
@@ -90,14 +547,15 @@ impl MetaGov {
 
     debug!("Start function started.");
 
-    if let Some(proposals) = self.fetcher.fetch_proposals().await {
+    if let Some(mut proposals) = self.fetcher.fetch_proposals().await {
       debug!("Fetched {:?} proposals.", proposals.len());
 
       let mut new_proposals = Vec::new();
+      let mut closed_proposals = Vec::new();
 
       if let Some(old_proposals) = self
         .cache
-        .get::<Vec<Proposal>>("meta_gov:proposals")
+        .get::<Vec<Proposal>>(&self.space.cache_key("proposals"))
         .await?
       {
         let old_ids: Vec<_> = old_proposals.iter().map(|proposal| &proposal.id).collect();
@@ -119,32 +577,97 @@ impl MetaGov {
             }
           }
         }
+
+        let active_ids: Vec<_> = proposals.iter().map(|proposal| &proposal.id).collect();
+        let ended_proposals: Vec<_> = old_proposals
+          .iter()
+          .filter(|proposal| !active_ids.contains(&&proposal.id))
+          .cloned()
+          .collect();
+
+        debug!("Found {:?} ended proposals.", ended_proposals.len());
+
+        if !ended_proposals.is_empty() {
+          let ended_ids: Vec<_> = ended_proposals
+            .iter()
+            .map(|proposal| proposal.id.clone())
+            .collect();
+
+          match self.fetcher.fetch_results(&ended_ids).await {
+            Some(results) => {
+              for proposal in ended_proposals {
+                match results
+                  .iter()
+                  .find(|result| result.proposal_id == proposal.id)
+                {
+                  Some(result) if result.is_final() => {
+                    info!("Handling a closed proposal... ({:?})", proposal.id);
+                    for handler in &self.handlers {
+                      if let Err(err) = handler.handle_proposal_result(&proposal, result).await {
+                        error!("Failed to handle proposal result: {:?}", err);
+                      } else {
+                        debug!("Successfully handled proposal result: {:?}", proposal.id);
+                      }
+                    }
+                    if let Err(err) = self.track_delegate_vote(&proposal, result).await {
+                      error!("Failed to track delegate vote: {:?}", err);
+                    }
+                    closed_proposals.push(proposal);
+                  }
+                  // Scores are still being computed, keep it around for the next run.
+                  Some(_) => proposals.push(proposal),
+                  None => {
+                    warn!("Proposal no longer exists: {:?}", proposal.id);
+                    closed_proposals.push(proposal);
+                  }
+                }
+              }
+            }
+            None => {
+              warn!("Failed to fetch proposal results");
+              proposals.extend(ended_proposals);
+            }
+          }
+        }
       }
 
-      if !new_proposals.is_empty() {
-        self.cache.put("meta_gov:proposals", &proposals).await;
+      if !new_proposals.is_empty() || !closed_proposals.is_empty() {
+        self
+          .cache
+          .put(&self.space.cache_key("proposals"), &proposals)
+          .await;
         info!("Updated proposals in cache");
       }
+
+      if let Err(err) = self.send_reminders(&proposals).await {
+        error!("Failed to send proposal reminders: {:?}", err);
+      }
     } else {
       warn!("Failed to fetch proposals");
     }
 
-    if let Some(votes) = self.fetcher.fetch_votes().await {
-      debug!("Fetched {:?} votes.", votes.len());
-
-      let mut new_votes = Vec::new();
+    if let Some(mut cursor) = self
+      .cache
+      .get::<VoteCursor>(&self.space.cache_key("votes:cursor"))
+      .await?
+    {
+      if let Some(votes) = self.fetcher.fetch_votes(cursor.created).await {
+        debug!("Fetched {:?} votes.", votes.len());
 
-      if let Some(old_votes) = self.cache.get::<Vec<Vote>>("meta_gov:votes").await? {
-        let old_ids: Vec<_> = old_votes.iter().map(|vote| &vote.id).collect();
-        new_votes = votes
+        let new_votes: Vec<_> = votes
           .iter()
-          .filter(|vote| !old_ids.contains(&&vote.id))
+          .filter(|vote| !cursor.ids.contains(&vote.id))
           .cloned()
           .collect();
 
         debug!("Found {:?} new votes.", new_votes.len());
 
         for vote in &new_votes {
+          if vote.vp < self.min_vp {
+            debug!("Skipping vote below voting power threshold: {:?}", vote.id);
+            continue;
+          }
+
           info!("Handling a new vote...");
           for handler in &self.handlers {
             if let Err(err) = handler.handle_new_vote(vote).await {
@@ -154,18 +677,212 @@ impl MetaGov {
             }
           }
         }
-      }
 
-      if !new_votes.is_empty() {
-        self.cache.put("meta_gov:votes", &votes).await;
-        info!("Updated votes in cache");
+        if !new_votes.is_empty() {
+          cursor.advance(&new_votes);
+          self
+            .cache
+            .put(&self.space.cache_key("votes:cursor"), &cursor)
+            .await;
+          info!("Updated vote cursor in cache");
+        }
+      } else {
+        warn!("Failed to fetch votes");
       }
     } else {
-      warn!("Failed to fetch votes");
+      warn!("Failed to get vote cursor");
     }
 
+    self.check_delegate_votes().await?;
+
     debug!("Start function finished.");
 
     Ok(())
   }
+
+  /// Reminds of proposals closing within one of the configured offsets, once
+  /// per offset and proposal.
+  async fn send_reminders(&self, proposals: &[Proposal]) -> Result<()> {
+    if self.reminder_hours.is_empty() {
+      return Ok(());
+    }
+
+    let now = Utc::now().timestamp();
+    let active_proposals: Vec<_> = proposals
+      .iter()
+      .filter(|proposal| proposal.end > now)
+      .collect();
+
+    let old_reminders = self
+      .cache
+      .get::<HashMap<String, Vec<i64>>>(&self.space.cache_key("proposals:reminders"))
+      .await?
+      .unwrap_or_default();
+
+    let mut reminders: HashMap<String, Vec<i64>> = old_reminders
+      .iter()
+      .filter(|(id, _)| active_proposals.iter().any(|proposal| &proposal.id == *id))
+      .map(|(id, hours)| (id.clone(), hours.clone()))
+      .collect();
+
+    let mut due_reminders = Vec::new();
+
+    for proposal in &active_proposals {
+      let sent_hours = reminders.entry(proposal.id.clone()).or_default();
+      let due_hours: Vec<_> = self
+        .reminder_hours
+        .iter()
+        .filter(|hours| !sent_hours.contains(hours))
+        .filter(|hours| now >= proposal.end - *hours * 60 * 60)
+        .copied()
+        .collect();
+
+      // A single reminder is sent when several offsets are due at once.
+      if !due_hours.is_empty() {
+        due_reminders.push(*proposal);
+        sent_hours.extend(due_hours.iter());
+      }
+    }
+
+    if !due_reminders.is_empty() {
+      let ids: Vec<_> = due_reminders
+        .iter()
+        .map(|proposal| proposal.id.clone())
+        .collect();
+
+      let Some(results) = self.fetcher.fetch_results(&ids).await else {
+        warn!("Failed to fetch proposal results");
+        return Ok(());
+      };
+
+      for proposal in &due_reminders {
+        let Some(result) = results
+          .iter()
+          .find(|result| result.proposal_id == proposal.id)
+        else {
+          continue;
+        };
+
+        info!("Handling a proposal reminder... ({:?})", proposal.id);
+        for handler in &self.handlers {
+          if let Err(err) = handler
+            .handle_proposal_reminder(proposal, result, proposal.end - now)
+            .await
+          {
+            error!("Failed to handle proposal reminder: {:?}", err);
+          } else {
+            debug!("Successfully handled proposal reminder: {:?}", proposal.id);
+          }
+        }
+      }
+    }
+
+    if reminders != old_reminders {
+      self
+        .cache
+        .put(&self.space.cache_key("proposals:reminders"), &reminders)
+        .await;
+      info!("Updated proposal reminders in cache");
+    }
+
+    Ok(())
+  }
+
+  /// Starts watching the linked Nouns DAO proposal of a closed Snapshot
+  /// proposal for the delegate's vote.
+  async fn track_delegate_vote(&self, proposal: &Proposal, result: &ProposalResult) -> Result<()> {
+    if self.space.nouns_delegate.is_none() {
+      return Ok(());
+    }
+
+    let info = self.space.proposal_info(proposal);
+    if info.excerpt.is_some() {
+      debug!("Proposal is not linked to Nouns DAO: {:?}", proposal.id);
+      return Ok(());
+    }
+
+    let mut pending_votes = self
+      .cache
+      .get::<Vec<PendingDelegateVote>>(&self.space.cache_key("delegate_votes"))
+      .await?
+      .unwrap_or_default();
+
+    pending_votes.push(PendingDelegateVote {
+      proposal: proposal.clone(),
+      nouns_proposal_id: info.key,
+      outcome: result.winner().filter(|_| result.quorum_reached()),
+      closed: Utc::now().timestamp(),
+    });
+
+    self
+      .cache
+      .put(&self.space.cache_key("delegate_votes"), &pending_votes)
+      .await;
+
+    Ok(())
+  }
+
+  /// Announces the delegate's votes cast on linked Nouns DAO proposals.
+  async fn check_delegate_votes(&self) -> Result<()> {
+    let Some(delegate) = &self.space.nouns_delegate else {
+      return Ok(());
+    };
+
+    let pending_votes = self
+      .cache
+      .get::<Vec<PendingDelegateVote>>(&self.space.cache_key("delegate_votes"))
+      .await?
+      .unwrap_or_default();
+
+    if pending_votes.is_empty() {
+      return Ok(());
+    }
+
+    let mut remaining_votes = Vec::new();
+
+    for pending in &pending_votes {
+      match self
+        .fetcher
+        .fetch_delegate_vote(&pending.nouns_proposal_id, delegate)
+        .await
+      {
+        Some((Some(vote), _)) => {
+          info!(
+            "Handling a delegate vote... ({:?})",
+            pending.nouns_proposal_id
+          );
+          for handler in &self.handlers {
+            if let Err(err) = handler.handle_delegate_vote(pending, &vote).await {
+              error!("Failed to handle delegate vote: {:?}", err);
+            } else {
+              debug!(
+                "Successfully handled delegate vote: {:?}",
+                pending.nouns_proposal_id
+              );
+            }
+          }
+        }
+        Some((None, true)) => {
+          info!(
+            "Delegate did not vote on proposal: {:?}",
+            pending.nouns_proposal_id
+          );
+        }
+        _ if Utc::now().timestamp() - pending.closed > DELEGATE_VOTE_WATCH_PERIOD => {
+          info!("Stopped watching proposal: {:?}", pending.nouns_proposal_id);
+        }
+        _ => remaining_votes.push(pending.clone()),
+      }
+    }
+
+    if remaining_votes.len() != pending_votes.len() {
+      self
+        .cache
+        .put(&self.space.cache_key("delegate_votes"), &remaining_votes)
+        .await;
+      info!("Updated delegate votes in cache");
+    }
+
+    Ok(())
+  }
 }
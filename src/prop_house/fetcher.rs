@@ -1,9 +1,10 @@
+use chrono::DateTime as ChronoDateTime;
 use graphql_client::{reqwest::post_graphql, GraphQLQuery};
-use log::{debug, error};
+use log::{debug, error, warn};
 use reqwest::Client;
 use worker::{Env, Result};
 
-use crate::prop_house::{Auction, Proposal, Vote};
+use crate::prop_house::{Auction, AuctionStatus, Community, InfiniteProposal, Proposal, Vote};
 
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -15,6 +16,16 @@ use crate::prop_house::{Auction, Proposal, Vote};
 )]
 struct AuctionQuery;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+  schema_path = "graphql/schemas/prop_house_schema.graphql",
+  query_path = "graphql/queries/prop_house_query.graphql",
+  response_derives = "Clone",
+  skip_serializing_none,
+  deprecated = "warn"
+)]
+struct CommunityQuery;
+
 #[derive(GraphQLQuery)]
 #[graphql(
   schema_path = "graphql/schemas/prop_house_schema.graphql",
@@ -35,8 +46,145 @@ struct ProposalQuery;
 )]
 struct VoteQuery;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+  schema_path = "graphql/schemas/prop_house_schema.graphql",
+  query_path = "graphql/queries/prop_house_query.graphql",
+  response_derives = "Clone",
+  skip_serializing_none,
+  deprecated = "warn"
+)]
+struct AuctionActivityQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+  schema_path = "graphql/schemas/prop_house_schema.graphql",
+  query_path = "graphql/queries/prop_house_query.graphql",
+  response_derives = "Clone",
+  skip_serializing_none,
+  deprecated = "warn"
+)]
+struct InfiniteProposalIdsQuery;
+
+#[derive(GraphQLQuery)]
+#[graphql(
+  schema_path = "graphql/schemas/prop_house_schema.graphql",
+  query_path = "graphql/queries/prop_house_query.graphql",
+  response_derives = "Clone",
+  skip_serializing_none,
+  deprecated = "warn"
+)]
+struct InfiniteProposalQuery;
+
 type DateTime = String;
 
+/// Converts a GraphQL date into a unix timestamp, zero when it can't be read.
+fn timestamp(date: &str) -> i64 {
+  ChronoDateTime::parse_from_rfc3339(date)
+    .map(|date| date.timestamp())
+    .unwrap_or_default()
+}
+
+impl From<&auction_query::AuctionFragment> for Auction {
+  fn from(auction: &auction_query::AuctionFragment) -> Self {
+    Self {
+      id: auction.id.try_into().unwrap(),
+      title: auction.title.clone(),
+      description: auction.description.clone(),
+      status: match &auction.status {
+        auction_query::AuctionStatus::Upcoming => Some(AuctionStatus::Upcoming),
+        auction_query::AuctionStatus::Open => Some(AuctionStatus::Open),
+        auction_query::AuctionStatus::Voting => Some(AuctionStatus::Voting),
+        auction_query::AuctionStatus::Closed => Some(AuctionStatus::Closed),
+        auction_query::AuctionStatus::Other(_) => None,
+      },
+      start_time: auction.start_time.clone(),
+      proposal_end_time: auction.proposal_end_time.clone(),
+      voting_end_time: auction.voting_end_time.clone(),
+      funding_amount: auction.funding_amount,
+      currency_type: auction.currency_type.clone(),
+      num_winners: auction.num_winners,
+    }
+  }
+}
+
+impl From<&auction_activity_query::AuctionFragment> for Auction {
+  fn from(auction: &auction_activity_query::AuctionFragment) -> Self {
+    Self {
+      id: auction.id.try_into().unwrap(),
+      title: auction.title.clone(),
+      description: auction.description.clone(),
+      status: match &auction.status {
+        auction_activity_query::AuctionStatus::Upcoming => Some(AuctionStatus::Upcoming),
+        auction_activity_query::AuctionStatus::Open => Some(AuctionStatus::Open),
+        auction_activity_query::AuctionStatus::Voting => Some(AuctionStatus::Voting),
+        auction_activity_query::AuctionStatus::Closed => Some(AuctionStatus::Closed),
+        auction_activity_query::AuctionStatus::Other(_) => None,
+      },
+      start_time: auction.start_time.clone(),
+      proposal_end_time: auction.proposal_end_time.clone(),
+      voting_end_time: auction.voting_end_time.clone(),
+      funding_amount: auction.funding_amount,
+      currency_type: auction.currency_type.clone(),
+      num_winners: auction.num_winners,
+    }
+  }
+}
+
+impl From<(isize, &proposal_query::ProposalFragment)> for Proposal {
+  fn from((auction_id, proposal): (isize, &proposal_query::ProposalFragment)) -> Self {
+    Self {
+      id: proposal.id.try_into().unwrap(),
+      title: proposal.title.clone(),
+      tldr: proposal.tldr.clone(),
+      address: proposal.address.clone(),
+      auction_id,
+      vote_count: proposal.vote_count_for,
+      created: timestamp(&proposal.created_date),
+    }
+  }
+}
+
+impl From<(isize, &auction_activity_query::ProposalFragment)> for Proposal {
+  fn from((auction_id, proposal): (isize, &auction_activity_query::ProposalFragment)) -> Self {
+    Self {
+      id: proposal.id.try_into().unwrap(),
+      title: proposal.title.clone(),
+      tldr: proposal.tldr.clone(),
+      address: proposal.address.clone(),
+      auction_id,
+      vote_count: proposal.vote_count_for,
+      created: timestamp(&proposal.created_date),
+    }
+  }
+}
+
+impl From<&vote_query::VoteFragment> for Vote {
+  fn from(vote: &vote_query::VoteFragment) -> Self {
+    Self {
+      id: vote.id.try_into().unwrap(),
+      address: vote.address.clone(),
+      auction_id: vote.auction_id.try_into().unwrap(),
+      proposal_id: vote.proposal_id.try_into().unwrap(),
+      direction: vote.direction.try_into().unwrap(),
+      weight: vote.weight,
+    }
+  }
+}
+
+impl From<&auction_activity_query::VoteFragment> for Vote {
+  fn from(vote: &auction_activity_query::VoteFragment) -> Self {
+    Self {
+      id: vote.id.try_into().unwrap(),
+      address: vote.address.clone(),
+      auction_id: vote.auction_id.try_into().unwrap(),
+      proposal_id: vote.proposal_id.try_into().unwrap(),
+      direction: vote.direction.try_into().unwrap(),
+      weight: vote.weight,
+    }
+  }
+}
+
 pub struct GraphQLFetcher {
   graphql_url: String,
   community_id: String,
@@ -50,9 +198,9 @@ impl GraphQLFetcher {
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<GraphQLFetcher> {
+  pub fn new_from_env(env: &Env, community: &Community) -> Result<GraphQLFetcher> {
     let graphql_url = env.var("PROP_HOUSE_GRAPHQL_URL")?.to_string();
-    let community_id = env.var("PROP_HOUSE_COMMUNITY_ID")?.to_string();
+    let community_id = community.id.to_string();
 
     Ok(Self::new(graphql_url, community_id))
   }
@@ -76,7 +224,26 @@ impl GraphQLFetcher {
         debug!("Failure details: {:?}", e);
       })
       .ok()
-      .and_then(|response| response.data)
+      .and_then(|response| {
+        if let Some(errors) = &response.errors {
+          error!("GraphQL request returned errors: {:?}", errors);
+        }
+        response.data
+      })
+  }
+
+  /// Returns the community name and profile image URL.
+  pub async fn fetch_community_profile(&self) -> Option<(String, String)> {
+    let variables = community_query::Variables {
+      id: self.community_id.parse().unwrap(),
+    };
+
+    let response = self.fetch::<CommunityQuery>(variables).await?;
+
+    Some((
+      response.community.name.clone(),
+      response.community.profile_image_url.clone(),
+    ))
   }
 
   pub async fn fetch_auctions(&self) -> Option<Vec<Auction>> {
@@ -89,11 +256,7 @@ impl GraphQLFetcher {
       .community
       .auctions
       .iter()
-      .map(|auction| Auction {
-        id: auction.id.try_into().unwrap(),
-        title: auction.title.clone(),
-        description: auction.description.clone(),
-      })
+      .map(Auction::from)
       .collect();
 
     Some(auctions)
@@ -116,13 +279,7 @@ impl GraphQLFetcher {
           .iter()
           .map(move |proposal| (auction.auction_fragment.id.try_into().unwrap(), proposal))
       })
-      .map(|(auction_id, proposal)| Proposal {
-        id: proposal.id.try_into().unwrap(),
-        title: proposal.title.clone(),
-        tldr: proposal.tldr.clone(),
-        address: proposal.address.clone(),
-        auction_id,
-      })
+      .map(Proposal::from)
       .collect();
 
     Some(proposals)
@@ -141,15 +298,124 @@ impl GraphQLFetcher {
       .iter()
       .flat_map(|auction| &auction.proposals)
       .flat_map(|proposal| &proposal.votes)
-      .map(|vote| Vote {
-        id: vote.id.try_into().unwrap(),
-        address: vote.address.clone(),
-        auction_id: vote.auction_id.try_into().unwrap(),
-        proposal_id: vote.proposal_id.try_into().unwrap(),
-        direction: vote.direction.try_into().unwrap(),
-      })
+      .map(Vote::from)
       .collect();
 
     Some(votes)
   }
+
+  /// Fetches the upcoming, open and voting auctions of the community along
+  /// with their proposals and votes, one request per active auction.
+  pub async fn fetch_active_auctions(&self) -> Option<Vec<(Auction, Vec<Proposal>, Vec<Vote>)>> {
+    let auctions = self.fetch_auctions().await?;
+    let mut active_auctions = Vec::new();
+
+    for auction in auctions {
+      if !matches!(
+        auction.status,
+        Some(AuctionStatus::Upcoming | AuctionStatus::Open | AuctionStatus::Voting)
+      ) {
+        continue;
+      }
+
+      if let Some(activity) = self.fetch_auction_activity(auction.id).await {
+        active_auctions.push(activity);
+      } else {
+        warn!("Failed to fetch activity of auction: {:?}", auction.id);
+      }
+    }
+
+    Some(active_auctions)
+  }
+
+  /// Fetches a single auction along with its proposals and votes.
+  pub async fn fetch_auction_activity(
+    &self,
+    auction_id: isize,
+  ) -> Option<(Auction, Vec<Proposal>, Vec<Vote>)> {
+    let variables = auction_activity_query::Variables {
+      id: auction_id as i64,
+    };
+
+    let response = self.fetch::<AuctionActivityQuery>(variables).await?;
+    let auction = &response.auction;
+
+    let proposals = auction
+      .proposals
+      .iter()
+      .map(|proposal| Proposal::from((auction_id, &proposal.proposal_fragment)))
+      .collect();
+
+    let votes = auction
+      .proposals
+      .iter()
+      .flat_map(|proposal| &proposal.votes)
+      .map(Vote::from)
+      .collect();
+
+    Some((Auction::from(&auction.auction_fragment), proposals, votes))
+  }
+
+  /// Returns ids and creation dates of infinite round proposals created since
+  /// the given date.
+  pub async fn fetch_infinite_proposal_ids(&self, since: &str) -> Option<Vec<(isize, String)>> {
+    let variables = infinite_proposal_ids_query::Variables {
+      since: Some(since.to_string()),
+    };
+
+    let response = self.fetch::<InfiniteProposalIdsQuery>(variables).await?;
+
+    let ids = response
+      .proposals_between
+      .iter()
+      .filter(|proposal| proposal.parent_type != "auction")
+      .map(|proposal| {
+        (
+          proposal.id.try_into().unwrap(),
+          proposal.created_date.clone(),
+        )
+      })
+      .collect();
+
+    Some(ids)
+  }
+
+  /// Fetches the given infinite round proposals one at a time, skipping those
+  /// of other communities and those that can't be fetched, such as deleted
+  /// proposals.
+  pub async fn fetch_infinite_proposals(&self, ids: &[isize]) -> Vec<InfiniteProposal> {
+    let community_id: i64 = self.community_id.parse().unwrap();
+    let mut proposals = Vec::new();
+
+    for id in ids {
+      let variables = infinite_proposal_query::Variables { id: *id as i64 };
+      let Some(response) = self.fetch::<InfiniteProposalQuery>(variables).await else {
+        warn!("Failed to fetch infinite proposal: {:?}", id);
+        continue;
+      };
+
+      let proposal = response.proposal;
+      if proposal.auction.community.id != community_id {
+        continue;
+      }
+
+      let created = timestamp(&proposal.created_date);
+      proposals.push(InfiniteProposal {
+        id: proposal.id.try_into().unwrap(),
+        title: proposal.title.clone(),
+        address: proposal.address.clone(),
+        auction_id: proposal.auction.id.try_into().unwrap(),
+        auction_title: proposal.auction.title.clone(),
+        req_amount: proposal.req_amount,
+        currency_type: proposal.auction.currency_type.clone(),
+        vote_count_for: proposal.vote_count_for,
+        vote_count_against: proposal.vote_count_against,
+        quorum_for: proposal.auction.quorum_for,
+        quorum_against: proposal.auction.quorum_against,
+        voting_end: created + proposal.auction.voting_period as i64,
+      });
+    }
+
+    proposals
+  }
 }
@@ -7,38 +7,53 @@ use worker::{Env, Error, Result};
 
 use crate::{
   cache::Cache,
-  prop_house::{handler::Handler, Auction, Proposal, Vote},
-  utils::{ens::get_domain_name, get_explorer_address, get_short_address},
+  prop_house::{
+    handler::Handler,
+    Auction,
+    AuctionStatus,
+    Community,
+    InfiniteProposal,
+    Proposal,
+    Vote,
+  },
+  utils::{ens::get_domain_name, get_explorer_address, get_short_address, get_slug},
 };
 
 pub struct DiscordHandler {
-  base_url: String,
+  community: Community,
   webhook_url: String,
   cache: Cache,
   client: Client,
 }
 
 impl DiscordHandler {
-  pub fn new(base_url: String, webhook_url: String, cache: Cache, client: Client) -> Self {
+  pub fn new(community: Community, webhook_url: String, cache: Cache, client: Client) -> Self {
     Self {
-      base_url,
+      community,
       webhook_url,
       cache,
       client,
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<DiscordHandler> {
-    let base_url = env.var("PROP_HOUSE_BASE_URL")?.to_string();
-    let webhook_url = env.secret("PROP_HOUSE_DISCORD_WEBHOOK_URL")?.to_string();
+  pub fn new_from_env(env: &Env, community: &Community) -> Result<DiscordHandler> {
+    let webhook_secret = community
+      .discord_webhook_secret
+      .as_ref()
+      .ok_or("Discord webhook secret not configured")?;
+    let webhook_url = env.secret(webhook_secret)?.to_string();
 
     let cache = Cache::new_from_env(env);
     let client = Client::new();
 
-    Ok(Self::new(base_url, webhook_url, cache, client))
+    Ok(Self::new(community.clone(), webhook_url, cache, client))
   }
 
-  async fn execute_webhook(&self, embed: Value) -> Result<()> {
+  async fn execute_webhook(&self, mut embed: Value) -> Result<()> {
+    if !self.community.profile_image_url.is_empty() {
+      embed["thumbnail"] = json!({"url": self.community.profile_image_url});
+    }
+
     let msg_json = json!({
       "username": "Raven",
       "avatar_url": "https://i.imgur.com/351ZnK2.png",
@@ -66,14 +81,11 @@ impl Handler for DiscordHandler {
   async fn handle_new_auction(&self, auction: &Auction) -> Result<()> {
     info!("Handling new auction: {}", auction.title);
 
-    let url = format!(
-      "{}/{}",
-      self.base_url,
-      auction.title.replace(' ', "-").to_lowercase()
-    );
+    let url = format!("{}/{}", self.community.base_url, get_slug(&auction.title));
     let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
     let description = format!(
-      "A new Prop House round has been created: “{}”",
+      "A new {} round has been created: “{}”",
+      self.community.label(),
       auction.title
     );
 
@@ -95,20 +107,20 @@ impl Handler for DiscordHandler {
 
     let auctions = self
       .cache
-      .get::<Vec<Auction>>("prop_house:auctions")
+      .get::<Vec<Auction>>(&self.community.cache_key("auctions"))
       .await?
-      .unwrap();
+      .unwrap_or_default();
 
     let auction = auctions
       .iter()
       .find(|&a| a.id == proposal.auction_id)
-      .unwrap()
-      .clone();
+      .cloned()
+      .ok_or("Auction not found")?;
 
     let url = format!(
       "{}/{}/{}",
-      self.base_url,
-      auction.title.replace(' ', "-").to_lowercase(),
+      self.community.base_url,
+      get_slug(&auction.title),
       proposal.id
     );
     let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
@@ -116,7 +128,8 @@ impl Handler for DiscordHandler {
       .await
       .unwrap_or(get_short_address(&proposal.address));
     let description = format!(
-      "A new Prop House proposal has been created: “{}”",
+      "A new {} proposal has been created: “{}”",
+      self.community.label(),
       proposal.title
     );
     let explorer = get_explorer_address(&proposal.address);
@@ -138,25 +151,37 @@ impl Handler for DiscordHandler {
     Ok(())
   }
 
-  async fn handle_new_vote(&self, vote: &Vote) -> Result<()> {
+  async fn handle_new_vote(&self, vote: &Vote, rank: usize) -> Result<()> {
     info!("Handling new vote from address: {}", vote.address);
 
     let proposals = self
       .cache
-      .get::<Vec<Proposal>>("prop_house:proposals")
+      .get::<Vec<Proposal>>(&self.community.cache_key("proposals"))
       .await?
-      .unwrap();
+      .unwrap_or_default();
 
     let proposal = proposals
       .iter()
       .find(|&a| a.id == vote.proposal_id)
-      .unwrap()
-      .clone();
+      .cloned()
+      .ok_or("Proposal not found")?;
+
+    let auctions = self
+      .cache
+      .get::<Vec<Auction>>(&self.community.cache_key("auctions"))
+      .await?
+      .unwrap_or_default();
+
+    let auction = auctions
+      .iter()
+      .find(|&a| a.id == proposal.auction_id)
+      .cloned()
+      .ok_or("Auction not found")?;
 
     let url = format!(
       "{}/{}/{}",
-      self.base_url,
-      proposal.title.replace(' ', "-").to_lowercase(),
+      self.community.base_url,
+      get_slug(&auction.title),
       proposal.id
     );
     let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
@@ -165,13 +190,15 @@ impl Handler for DiscordHandler {
       .unwrap_or(get_short_address(&vote.address));
 
     let description = format!(
-      "{} has voted {} “{}” proposal.",
+      "{} allocated {} {} “{}” proposal, now ranked #{} in its round.",
       wallet,
+      vote.describe_weight(),
       match vote.direction {
-        1 => "for",
+        1 => "to",
         _ => "against",
       },
-      proposal.title
+      proposal.title,
+      rank
     );
     let explorer = get_explorer_address(&vote.address);
 
@@ -191,4 +218,161 @@ impl Handler for DiscordHandler {
 
     Ok(())
   }
+
+  async fn handle_auction_status(&self, auction: &Auction) -> Result<()> {
+    let Some(description) = auction.describe_status() else {
+      return Ok(());
+    };
+
+    info!("Handling auction status change: {}", auction.title);
+
+    let url = format!("{}/{}", self.community.base_url, get_slug(&auction.title));
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let title = match auction.status {
+      Some(AuctionStatus::Voting) => "Prop House Round Voting",
+      Some(AuctionStatus::Closed) => "Prop House Round Closed",
+      _ => "Prop House Round Open",
+    };
+
+    let embed = json!({
+        "title": title,
+        "description": description,
+        "url": url,
+        "color": 0x8A2CE2,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_auction_reminder(&self, auction: &Auction, stage: AuctionStatus) -> Result<()> {
+    info!("Handling auction reminder: {}", auction.title);
+
+    let url = format!("{}/{}", self.community.base_url, get_slug(&auction.title));
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let description = auction.describe_reminder(stage);
+
+    let embed = json!({
+        "title": "Prop House Round Closing Soon",
+        "description": description,
+        "url": url,
+        "color": 0x8A2CE2,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_auction_winners(&self, auction: &Auction, winners: &[Proposal]) -> Result<()> {
+    info!("Handling auction winners: {}", auction.title);
+
+    let url = format!("{}/{}", self.community.base_url, get_slug(&auction.title));
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+
+    let mut lines = Vec::new();
+    for (index, winner) in winners.iter().enumerate() {
+      let wallet = get_domain_name(&winner.address)
+        .await
+        .unwrap_or(get_short_address(&winner.address));
+      lines.push(format!(
+        "{}. “{}” by {} with {}, receiving {} {}",
+        index + 1,
+        winner.title,
+        wallet,
+        winner.describe_votes(),
+        auction.funding_amount,
+        auction.currency_type
+      ));
+    }
+
+    let description = format!(
+      "The winners of “{}” round have been decided.\n\n{}",
+      auction.title,
+      lines.join("\n")
+    );
+
+    let embed = json!({
+        "title": "Prop House Round Winners",
+        "description": description,
+        "url": url,
+        "color": 0x8A2CE2,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_new_infinite_proposal(&self, proposal: &InfiniteProposal) -> Result<()> {
+    info!("Handling new infinite proposal: {}", proposal.title);
+
+    let url = format!(
+      "{}/{}/{}",
+      self.community.base_url,
+      get_slug(&proposal.auction_title),
+      proposal.id
+    );
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let wallet = get_domain_name(&proposal.address)
+      .await
+      .unwrap_or(get_short_address(&proposal.address));
+    let description = format!(
+      "A new {} proposal has been created: “{}”, requesting {} {}.",
+      self.community.label(),
+      proposal.title,
+      proposal.req_amount,
+      proposal.currency_type
+    );
+    let explorer = get_explorer_address(&proposal.address);
+
+    let embed = json!({
+        "title": "New Prop House Proposal",
+        "description": description,
+        "url": url,
+        "color": 0x8A2CE2,
+        "footer": {"text": date},
+        "author": {
+            "name": wallet,
+            "url": explorer,
+        }
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_infinite_proposal_result(&self, proposal: &InfiniteProposal) -> Result<()> {
+    info!("Handling infinite proposal result: {}", proposal.title);
+
+    let url = format!(
+      "{}/{}/{}",
+      self.community.base_url,
+      get_slug(&proposal.auction_title),
+      proposal.id
+    );
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let title = if proposal.is_funded() {
+      "Prop House Proposal Funded"
+    } else {
+      "Prop House Proposal Rejected"
+    };
+
+    let embed = json!({
+        "title": title,
+        "description": proposal.outcome(),
+        "url": url,
+        "color": 0x8A2CE2,
+        "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
 }
@@ -12,12 +12,20 @@ use worker::{Env, Error, Result};
 
 use crate::{
   cache::Cache,
-  prop_house::{handler::Handler, Auction, Proposal, Vote},
-  utils::{ens::get_wallet_handle, link::Link},
+  prop_house::{
+    handler::Handler,
+    Auction,
+    AuctionStatus,
+    Community,
+    InfiniteProposal,
+    Proposal,
+    Vote,
+  },
+  utils::{ens::get_wallet_handle, get_slug, link::Link, text::truncate_bytes},
 };
 
 pub(crate) struct FarcasterHandler {
-  base_url: String,
+  community: Community,
   warpcast_url: String,
   bearer_token: String,
   channel_key: String,
@@ -28,7 +36,7 @@ pub(crate) struct FarcasterHandler {
 
 impl FarcasterHandler {
   pub fn new(
-    base_url: String,
+    community: Community,
     warpcast_url: String,
     bearer_token: String,
     channel_key: String,
@@ -37,7 +45,7 @@ impl FarcasterHandler {
     link: Link,
   ) -> Self {
     Self {
-      base_url,
+      community,
       warpcast_url,
       bearer_token,
       channel_key,
@@ -47,18 +55,17 @@ impl FarcasterHandler {
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<FarcasterHandler> {
-    let base_url = env.var("PROP_HOUSE_BASE_URL")?.to_string();
+  pub fn new_from_env(env: &Env, community: &Community) -> Result<FarcasterHandler> {
     let warpcast_url = env.var("WARP_CAST_API_BASE_URL")?.to_string();
     let bearer_token = env.secret("PROP_HOUSE_WARP_CAST_TOKEN")?.to_string();
-    let channel_key = env.var("PROP_HOUSE_WARP_CAST_CHANNEL")?.to_string();
+    let channel_key = community.warp_cast_channel.clone();
 
     let cache = Cache::new_from_env(env);
     let client = Client::new();
     let link = Link::new_from_env(&env);
 
     Ok(Self::new(
-      base_url,
+      community.clone(),
       warpcast_url,
       bearer_token,
       channel_key,
@@ -93,6 +100,9 @@ impl FarcasterHandler {
       })?;
 
     debug!("Response status: {:?}", response.status());
+    if !response.status().is_success() {
+      error!("Cast request failed with status: {:?}", response.status());
+    }
 
     Ok(response)
   }
@@ -103,13 +113,10 @@ impl Handler for FarcasterHandler {
   async fn handle_new_auction(&self, auction: &Auction) -> Result<()> {
     info!("Handling new auction: {}", auction.title);
 
-    let url = format!(
-      "{}/{}",
-      self.base_url,
-      auction.title.replace(' ', "-").to_lowercase()
-    );
+    let url = format!("{}/{}", self.community.base_url, get_slug(&auction.title));
     let description = format!(
-      "A new Prop House round has been created: “{}”",
+      "A new {} round has been created: “{}”",
+      self.community.label(),
       auction.title
     );
 
@@ -119,7 +126,42 @@ impl Handler for FarcasterHandler {
         "channelKey": self.channel_key
     });
 
-    self.make_http_request(request_data).await?;
+    let response = self.make_http_request(request_data).await.map_err(|e| {
+      error!("Failed to make HTTP request: {}", e);
+      return e;
+    })?;
+
+    let response_body = response.text().await.map_err(|e| {
+      error!("Failed to get text from response: {}", e);
+      Error::from(format!("Failed to get text from response: {}", e))
+    })?;
+
+    let parsed_body: serde_json::Result<Value> = serde_json::from_str(&response_body);
+
+    let response_body: Value = match parsed_body {
+      Ok(body) => body,
+      Err(e) => {
+        error!("Failed to parse JSON: {}", e);
+        return Err(e.into());
+      }
+    };
+
+    let cast_hash = response_body["result"]["cast"]["hash"]
+      .as_str()
+      .unwrap_or_default();
+
+    let mut auctions_casts = self
+      .cache
+      .get::<HashMap<isize, String>>(&self.community.cache_key("auctions:casts"))
+      .await?
+      .unwrap_or_default();
+
+    auctions_casts.insert(auction.id, cast_hash.to_string());
+
+    self
+      .cache
+      .put(&self.community.cache_key("auctions:casts"), &auctions_casts)
+      .await;
 
     Ok(())
   }
@@ -129,9 +171,9 @@ impl Handler for FarcasterHandler {
 
     let auctions = self
       .cache
-      .get::<Vec<Auction>>("prop_house:auctions")
+      .get::<Vec<Auction>>(&self.community.cache_key("auctions"))
       .await?
-      .unwrap();
+      .unwrap_or_default();
 
     let auction = auctions
       .iter()
@@ -141,16 +183,18 @@ impl Handler for FarcasterHandler {
 
     let url = format!(
       "{}/{}/{}",
-      self.base_url,
-      auction.title.replace(' ', "-").to_lowercase(),
+      self.community.base_url,
+      get_slug(&auction.title),
       proposal.id
     );
 
     let wallet = get_wallet_handle(&proposal.address, "xyz.farcaster").await;
 
     let description = format!(
-      "{} created a new proposal on Prop House: “{}”",
-      wallet, proposal.title
+      "{} created a new proposal on {}: “{}”",
+      wallet,
+      self.community.label(),
+      proposal.title
     );
 
     let request_data = json!({
@@ -185,7 +229,7 @@ impl Handler for FarcasterHandler {
 
     let mut proposals_casts = self
       .cache
-      .get::<HashMap<isize, String>>("prop_house:proposals:casts")
+      .get::<HashMap<isize, String>>(&self.community.cache_key("proposals:casts"))
       .await?
       .unwrap_or_default();
 
@@ -193,20 +237,23 @@ impl Handler for FarcasterHandler {
 
     self
       .cache
-      .put("prop_house:proposals:casts", &proposals_casts)
+      .put(
+        &self.community.cache_key("proposals:casts"),
+        &proposals_casts,
+      )
       .await;
 
     Ok(())
   }
 
-  async fn handle_new_vote(&self, vote: &Vote) -> Result<()> {
+  async fn handle_new_vote(&self, vote: &Vote, rank: usize) -> Result<()> {
     info!("Handling new vote from address: {}", vote.address);
 
     let proposals = self
       .cache
-      .get::<Vec<Proposal>>("prop_house:proposals")
+      .get::<Vec<Proposal>>(&self.community.cache_key("proposals"))
       .await?
-      .unwrap();
+      .unwrap_or_default();
 
     let proposal = proposals
       .iter()
@@ -216,7 +263,7 @@ impl Handler for FarcasterHandler {
 
     let proposals_casts = self
       .cache
-      .get::<HashMap<isize, String>>("prop_house:proposals:casts")
+      .get::<HashMap<isize, String>>(&self.community.cache_key("proposals:casts"))
       .await?
       .unwrap_or_default();
 
@@ -227,13 +274,15 @@ impl Handler for FarcasterHandler {
     let wallet = get_wallet_handle(&vote.address, "xyz.farcaster").await;
 
     let description = format!(
-      "{} has voted {} “{}” proposal.",
+      "{} allocated {} {} “{}” proposal, now ranked #{} in its round.",
       wallet,
+      vote.describe_weight(),
       match vote.direction {
-        1 => "for",
+        1 => "to",
         _ => "against",
       },
-      proposal.title
+      proposal.title,
+      rank
     );
 
     let request_data = json!({
@@ -246,4 +295,197 @@ impl Handler for FarcasterHandler {
 
     Ok(())
   }
+
+  async fn handle_auction_status(&self, auction: &Auction) -> Result<()> {
+    let Some(description) = auction.describe_status() else {
+      return Ok(());
+    };
+
+    info!("Handling auction status change: {}", auction.title);
+
+    let url = format!("{}/{}", self.community.base_url, get_slug(&auction.title));
+
+    let request_data = json!({
+        "text": description,
+        "embeds": [url],
+        "channelKey": self.channel_key
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_auction_reminder(&self, auction: &Auction, stage: AuctionStatus) -> Result<()> {
+    info!("Handling auction reminder: {}", auction.title);
+
+    let url = format!("{}/{}", self.community.base_url, get_slug(&auction.title));
+    let description = auction.describe_reminder(stage);
+
+    let request_data = json!({
+        "text": description,
+        "embeds": [url],
+        "channelKey": self.channel_key
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_auction_winners(&self, auction: &Auction, winners: &[Proposal]) -> Result<()> {
+    info!("Handling auction winners: {}", auction.title);
+
+    let url = format!("{}/{}", self.community.base_url, get_slug(&auction.title));
+
+    let mut lines = Vec::new();
+    for (index, winner) in winners.iter().enumerate() {
+      let wallet = get_wallet_handle(&winner.address, "xyz.farcaster").await;
+      lines.push(format!(
+        "{}. “{}” by {} ({}, {} {})",
+        index + 1,
+        winner.title,
+        wallet,
+        winner.describe_votes(),
+        auction.funding_amount,
+        auction.currency_type
+      ));
+    }
+
+    let description = truncate_bytes(
+      &format!(
+        "The winners of “{}” round have been decided:\n\n{}",
+        auction.title,
+        lines.join("\n")
+      ),
+      320,
+    );
+
+    let auctions_casts = self
+      .cache
+      .get::<HashMap<isize, String>>(&self.community.cache_key("auctions:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let request_data = match auctions_casts.get(&auction.id) {
+      Some(cast_hash) => json!({
+        "text": description,
+        "channelKey": self.channel_key,
+        "parent": {"hash": cast_hash},
+      }),
+      None => json!({
+        "text": description,
+        "embeds": [url],
+        "channelKey": self.channel_key
+      }),
+    };
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_new_infinite_proposal(&self, proposal: &InfiniteProposal) -> Result<()> {
+    info!("Handling new infinite proposal: {}", proposal.title);
+
+    let url = format!(
+      "{}/{}/{}",
+      self.community.base_url,
+      get_slug(&proposal.auction_title),
+      proposal.id
+    );
+
+    let wallet = get_wallet_handle(&proposal.address, "xyz.farcaster").await;
+
+    let description = format!(
+      "{} created a new proposal on {}: “{}”, requesting {} {}.",
+      wallet,
+      self.community.label(),
+      proposal.title,
+      proposal.req_amount,
+      proposal.currency_type
+    );
+
+    let request_data = json!({
+        "text": description,
+        "embeds": [url],
+        "channelKey": self.channel_key
+    });
+
+    let response = self.make_http_request(request_data).await.map_err(|e| {
+      error!("Failed to make HTTP request: {}", e);
+      e
+    })?;
+
+    let response_body = response.text().await.map_err(|e| {
+      error!("Failed to get text from response: {}", e);
+      Error::from(format!("Failed to get text from response: {}", e))
+    })?;
+
+    let parsed_body: serde_json::Result<Value> = serde_json::from_str(&response_body);
+
+    let response_body: Value = match parsed_body {
+      Ok(body) => body,
+      Err(e) => {
+        error!("Failed to parse JSON: {}", e);
+        return Err(e.into());
+      }
+    };
+
+    let cast_hash = response_body["result"]["cast"]["hash"]
+      .as_str()
+      .unwrap_or_default();
+
+    let mut proposals_casts = self
+      .cache
+      .get::<HashMap<isize, String>>(&self.community.cache_key("proposals:casts"))
+      .await?
+      .unwrap_or_default();
+
+    proposals_casts.insert(proposal.id, cast_hash.to_string());
+
+    self
+      .cache
+      .put(
+        &self.community.cache_key("proposals:casts"),
+        &proposals_casts,
+      )
+      .await;
+
+    Ok(())
+  }
+
+  async fn handle_infinite_proposal_result(&self, proposal: &InfiniteProposal) -> Result<()> {
+    info!("Handling infinite proposal result: {}", proposal.title);
+
+    let url = format!(
+      "{}/{}/{}",
+      self.community.base_url,
+      get_slug(&proposal.auction_title),
+      proposal.id
+    );
+
+    let proposals_casts = self
+      .cache
+      .get::<HashMap<isize, String>>(&self.community.cache_key("proposals:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let request_data = match proposals_casts.get(&proposal.id) {
+      Some(cast_hash) => json!({
+        "text": proposal.outcome(),
+        "channelKey": self.channel_key,
+        "parent": {"hash": cast_hash},
+      }),
+      None => json!({
+        "text": proposal.outcome(),
+        "embeds": [url],
+        "channelKey": self.channel_key
+      }),
+    };
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
 }
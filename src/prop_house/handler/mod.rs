@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use worker::Result;
 
-use crate::prop_house::{Auction, Proposal, Vote};
+use crate::prop_house::{Auction, AuctionStatus, InfiniteProposal, Proposal, Vote};
 
 pub(crate) mod discord;
 pub(crate) mod farcaster;
@@ -10,5 +10,10 @@ pub(crate) mod farcaster;
 pub trait Handler {
   async fn handle_new_auction(&self, auction: &Auction) -> Result<()>;
   async fn handle_new_proposal(&self, proposal: &Proposal) -> Result<()>;
-  async fn handle_new_vote(&self, vote: &Vote) -> Result<()>;
+  async fn handle_new_vote(&self, vote: &Vote, rank: usize) -> Result<()>;
+  async fn handle_auction_status(&self, auction: &Auction) -> Result<()>;
+  async fn handle_auction_reminder(&self, auction: &Auction, stage: AuctionStatus) -> Result<()>;
+  async fn handle_auction_winners(&self, auction: &Auction, winners: &[Proposal]) -> Result<()>;
+  async fn handle_new_infinite_proposal(&self, proposal: &InfiniteProposal) -> Result<()>;
+  async fn handle_infinite_proposal_result(&self, proposal: &InfiniteProposal) -> Result<()>;
 }
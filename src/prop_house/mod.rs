@@ -1,19 +1,191 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use chrono::{DateTime, Duration, Utc};
 use fetcher::GraphQLFetcher;
 use handler::{discord::DiscordHandler, farcaster::FarcasterHandler, Handler};
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
-use worker::{Env, Result};
+use worker::{Env, Error, Result};
 
-use crate::cache::Cache;
+use crate::{cache::Cache, utils::text::describe_duration};
 
 pub mod fetcher;
 pub mod handler;
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Community {
+  pub(crate) id: isize,
+  pub(crate) base_url: String,
+  /// Name of the secret holding the community Discord webhook URL.
+  #[serde(default)]
+  pub(crate) discord_webhook_secret: Option<String>,
+  #[serde(default)]
+  pub(crate) warp_cast_channel: String,
+  #[serde(default)]
+  pub(crate) cache_namespace: Option<String>,
+  /// Community name used to brand announcements, loaded from Prop House when
+  /// not configured.
+  #[serde(default)]
+  pub(crate) name: String,
+  #[serde(default)]
+  pub(crate) profile_image_url: String,
+}
+
+impl Community {
+  /// Reads the communities from `PROP_HOUSE_COMMUNITIES`, a JSON array, falling
+  /// back to the single community described by the `PROP_HOUSE_*` variables.
+  /// Missing names and images are loaded once from Prop House and cached.
+  pub async fn list_from_env(env: &Env) -> Result<Vec<Community>> {
+    let mut communities = if let Ok(communities) = env.var("PROP_HOUSE_COMMUNITIES") {
+      serde_json::from_str(&communities.to_string())
+        .map_err(|e| Error::from(format!("Failed to parse PROP_HOUSE_COMMUNITIES: {}", e)))?
+    } else {
+      vec![Community {
+        id: env
+          .var("PROP_HOUSE_COMMUNITY_ID")?
+          .to_string()
+          .parse()
+          .map_err(|_| Error::from("Invalid PROP_HOUSE_COMMUNITY_ID"))?,
+        base_url: env.var("PROP_HOUSE_BASE_URL")?.to_string(),
+        discord_webhook_secret: Some("PROP_HOUSE_DISCORD_WEBHOOK_URL".to_string()),
+        warp_cast_channel: env.var("PROP_HOUSE_WARP_CAST_CHANNEL")?.to_string(),
+        cache_namespace: Some("prop_house".to_string()),
+        name: String::new(),
+        profile_image_url: String::new(),
+      }]
+    };
+
+    let cache = Cache::new_from_env(env);
+
+    for community in &mut communities {
+      if !community.name.is_empty() && !community.profile_image_url.is_empty() {
+        continue;
+      }
+
+      let profile_key = community.cache_key("profile");
+      let mut profile = cache.get::<(String, String)>(&profile_key).await?;
+      if profile.is_none() {
+        let fetcher = GraphQLFetcher::new_from_env(env, community)?;
+        profile = fetcher.fetch_community_profile().await;
+        if let Some(profile) = &profile {
+          cache.put(&profile_key, profile).await;
+        }
+      }
+
+      if let Some((name, profile_image_url)) = profile {
+        if community.name.is_empty() {
+          community.name = name;
+        }
+        if community.profile_image_url.is_empty() {
+          community.profile_image_url = profile_image_url;
+        }
+      } else {
+        warn!("Failed to fetch community profile: {:?}", community.id);
+      }
+    }
+
+    Ok(communities)
+  }
+
+  /// Names the community's Prop House in announcements.
+  pub(crate) fn label(&self) -> String {
+    if self.name.is_empty() {
+      "Prop House".to_string()
+    } else {
+      format!("{} Prop House", self.name)
+    }
+  }
+
+  pub(crate) fn cache_key(&self, key: &str) -> String {
+    match &self.cache_namespace {
+      Some(namespace) => format!("{}:{}", namespace, key),
+      None => format!("prop_house:{}:{}", self.id, key),
+    }
+  }
+}
+
+/// Time before a round deadline at which a reminder is sent.
+const AUCTION_REMINDER_PERIOD: i64 = 24 * 60 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuctionStatus {
+  Upcoming,
+  Open,
+  Voting,
+  Closed,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Auction {
   pub id: isize,
   pub title: String,
   pub description: String,
+  #[serde(default)]
+  pub status: Option<AuctionStatus>,
+  #[serde(default)]
+  pub start_time: String,
+  #[serde(default)]
+  pub proposal_end_time: String,
+  #[serde(default)]
+  pub voting_end_time: String,
+  #[serde(default)]
+  pub funding_amount: f64,
+  #[serde(default)]
+  pub currency_type: String,
+  #[serde(default)]
+  pub num_winners: i64,
+}
+
+impl Auction {
+  /// Returns the deadline of the given stage as a unix timestamp.
+  pub(crate) fn deadline(&self, stage: AuctionStatus) -> Option<i64> {
+    let time = match stage {
+      AuctionStatus::Upcoming => &self.start_time,
+      AuctionStatus::Open => &self.proposal_end_time,
+      AuctionStatus::Voting | AuctionStatus::Closed => &self.voting_end_time,
+    };
+
+    DateTime::parse_from_rfc3339(time)
+      .ok()
+      .map(|time| time.timestamp())
+  }
+
+  /// Describes the round's current stage, if it is worth announcing.
+  pub(crate) fn describe_status(&self) -> Option<String> {
+    match self.status? {
+      AuctionStatus::Upcoming => None,
+      AuctionStatus::Open => Some(format!(
+        "Prop House round “{}” is now open for proposals.",
+        self.title
+      )),
+      AuctionStatus::Voting => Some(format!(
+        "Proposal submission has closed and voting is now open on “{}” round.",
+        self.title
+      )),
+      AuctionStatus::Closed => Some(format!("Voting has closed on “{}” round.", self.title)),
+    }
+  }
+
+  /// Describes the upcoming deadline of the given stage.
+  pub(crate) fn describe_reminder(&self, stage: AuctionStatus) -> String {
+    let remaining = self
+      .deadline(stage)
+      .map(|deadline| deadline - Utc::now().timestamp())
+      .unwrap_or(AUCTION_REMINDER_PERIOD);
+
+    match stage {
+      AuctionStatus::Open => format!(
+        "Proposal submission for “{}” round closes in {}.",
+        self.title,
+        describe_duration(remaining)
+      ),
+      _ => format!(
+        "Voting on “{}” round closes in {}.",
+        self.title,
+        describe_duration(remaining)
+      ),
+    }
+  }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -23,6 +195,62 @@ pub struct Proposal {
   pub tldr: String,
   pub address: String,
   pub auction_id: isize,
+  #[serde(default)]
+  pub vote_count: i64,
+  /// Unix timestamp, left at zero on proposals cached before it was tracked.
+  #[serde(default)]
+  pub created: i64,
+}
+
+impl Proposal {
+  pub(crate) fn describe_votes(&self) -> String {
+    match self.vote_count {
+      1 => "1 vote".to_string(),
+      vote_count => format!("{} votes", vote_count),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InfiniteProposal {
+  pub id: isize,
+  pub title: String,
+  pub address: String,
+  pub auction_id: isize,
+  pub auction_title: String,
+  pub req_amount: f64,
+  pub currency_type: String,
+  pub vote_count_for: i64,
+  pub vote_count_against: i64,
+  pub quorum_for: i64,
+  pub quorum_against: i64,
+  pub voting_end: i64,
+}
+
+impl InfiniteProposal {
+  pub(crate) fn is_funded(&self) -> bool {
+    self.vote_count_for >= self.quorum_for
+  }
+
+  pub(crate) fn is_rejected(&self) -> bool {
+    self.vote_count_against >= self.quorum_against
+  }
+
+  /// Describes whether the proposal reached the funding or the rejection
+  /// quorum.
+  pub(crate) fn outcome(&self) -> String {
+    if self.is_funded() {
+      format!(
+        "“{}” proposal has been funded with {} {} after reaching {} votes for.",
+        self.title, self.req_amount, self.currency_type, self.vote_count_for
+      )
+    } else {
+      format!(
+        "“{}” proposal has been rejected after reaching {} votes against.",
+        self.title, self.vote_count_against
+      )
+    }
+  }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -32,69 +260,137 @@ pub struct Vote {
   pub auction_id: isize,
   pub proposal_id: isize,
   pub direction: isize,
+  #[serde(default)]
+  pub weight: i64,
+}
+
+impl Vote {
+  pub(crate) fn describe_weight(&self) -> String {
+    match self.weight {
+      1 => "1 vote".to_string(),
+      weight => format!("{} votes", weight),
+    }
+  }
+
+  /// Returns the 1-based rank of the voted proposal within its round, ordering
+  /// the round's proposals by the total weight of their supporting votes.
+  pub(crate) fn proposal_rank(&self, votes: &[Vote]) -> usize {
+    let mut totals: HashMap<isize, i64> = HashMap::new();
+    for vote in votes
+      .iter()
+      .filter(|vote| vote.auction_id == self.auction_id && vote.direction == 1)
+    {
+      *totals.entry(vote.proposal_id).or_default() += vote.weight;
+    }
+
+    let total = totals.get(&self.proposal_id).copied().unwrap_or_default();
+    totals.values().filter(|other| **other > total).count() + 1
+  }
 }
 
 pub struct PropHouse {
+  community: Community,
   cache: Cache,
   fetcher: GraphQLFetcher,
   handlers: Vec<Box<dyn Handler>>,
 }
 
 impl PropHouse {
-  pub fn new(cache: Cache, fetcher: GraphQLFetcher, handlers: Vec<Box<dyn Handler>>) -> Self {
+  pub fn new(
+    community: Community,
+    cache: Cache,
+    fetcher: GraphQLFetcher,
+    handlers: Vec<Box<dyn Handler>>,
+  ) -> Self {
     Self {
+      community,
       cache,
       fetcher,
       handlers,
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<Self> {
+  pub fn new_from_env(env: &Env, community: Community) -> Result<Self> {
     let cache = Cache::new_from_env(env);
-    let fetcher = GraphQLFetcher::new_from_env(env)?;
+    let fetcher = GraphQLFetcher::new_from_env(env, &community)?;
     let mut handlers = vec![];
 
-    if env.var("PROP_HOUSE_DISCORD_ENABLED").unwrap().to_string() == "true" {
-      let discord_handler: Box<dyn Handler> = Box::new(DiscordHandler::new_from_env(env)?);
+    if env.var("PROP_HOUSE_DISCORD_ENABLED").unwrap().to_string() == "true"
+      && community.discord_webhook_secret.is_some()
+    {
+      let discord_handler: Box<dyn Handler> =
+        Box::new(DiscordHandler::new_from_env(env, &community)?);
       handlers.push(discord_handler);
     }
 
     if env.var("PROP_HOUSE_FARCASTER_ENABLED").unwrap().to_string() == "true" {
-      let farcaster_handler: Box<dyn Handler> = Box::new(FarcasterHandler::new_from_env(env)?);
+      let farcaster_handler: Box<dyn Handler> =
+        Box::new(FarcasterHandler::new_from_env(env, &community)?);
       handlers.push(farcaster_handler);
     }
 
-    Ok(Self::new(cache, fetcher, handlers))
+    Ok(Self::new(community, cache, fetcher, handlers))
   }
 
   pub async fn setup(&self) {
     debug!("Setup function started.");
 
-    if !self.cache.has("prop_house:auctions").await {
+    if !self.cache.has(&self.community.cache_key("auctions")).await {
       if let Some(auctions) = self.fetcher.fetch_auctions().await {
         info!("Fetched {:?} auctions.", auctions.len());
         debug!("Putting fetched auctions into cache.");
-        self.cache.put("prop_house:auctions", &auctions).await;
+        self
+          .cache
+          .put(&self.community.cache_key("auctions"), &auctions)
+          .await;
       } else {
         warn!("Failed to fetch auctions");
       }
     };
 
-    if !self.cache.has("prop_house:proposals").await {
+    if !self.cache.has(&self.community.cache_key("proposals")).await {
       if let Some(proposals) = self.fetcher.fetch_proposals().await {
         info!("Fetched {:?} proposals.", proposals.len());
         debug!("Putting fetched proposals into cache.");
-        self.cache.put("prop_house:proposals", &proposals).await;
+        self
+          .cache
+          .put(&self.community.cache_key("proposals"), &proposals)
+          .await;
       } else {
         warn!("Failed to fetch proposals");
       }
     };
 
-    if !self.cache.has("prop_house:votes").await {
+    if !self
+      .cache
+      .has(&self.community.cache_key("infinite_proposals:cursor"))
+      .await
+    {
+      debug!("Putting infinite proposals cursor into cache.");
+      self
+        .cache
+        .put(
+          &self.community.cache_key("infinite_proposals:cursor"),
+          &Utc::now().to_rfc3339(),
+        )
+        .await;
+      self
+        .cache
+        .put(
+          &self.community.cache_key("infinite_proposals"),
+          &Vec::<InfiniteProposal>::new(),
+        )
+        .await;
+    };
+
+    if !self.cache.has(&self.community.cache_key("votes")).await {
       if let Some(votes) = self.fetcher.fetch_votes().await {
         info!("Fetched {:?} votes.", votes.len());
         debug!("Putting fetched votes into cache.");
-        self.cache.put("prop_house:votes", &votes).await;
+        self
+          .cache
+          .put(&self.community.cache_key("votes"), &votes)
+          .await;
       } else {
         warn!("Failed to fetch votes");
       }
@@ -108,18 +404,57 @@ impl PropHouse {
 
     debug!("Start function started.");
 
-    if let Some(auctions) = self.fetcher.fetch_auctions().await {
-      debug!("Fetched {:?} auctions.", auctions.len());
+    let mut fetched_ids = Vec::new();
+    let mut proposals = Vec::new();
+    let mut votes = Vec::new();
+
+    if let Some(old_auctions) = self
+      .cache
+      .get::<Vec<Auction>>(&self.community.cache_key("auctions"))
+      .await?
+    {
+      if let Some(mut active_auctions) = self.fetcher.fetch_active_auctions().await {
+        debug!("Fetched {:?} active auctions.", active_auctions.len());
+
+        // Auctions leaving the active statuses are fetched one last time, for
+        // their final status and votes.
+        let leaving_ids: Vec<_> = old_auctions
+          .iter()
+          .filter(|old_auction| {
+            matches!(
+              old_auction.status,
+              Some(AuctionStatus::Upcoming | AuctionStatus::Open | AuctionStatus::Voting)
+            ) && !active_auctions
+              .iter()
+              .any(|(auction, _, _)| auction.id == old_auction.id)
+          })
+          .map(|old_auction| old_auction.id)
+          .collect();
+
+        for auction_id in leaving_ids {
+          if let Some(activity) = self.fetcher.fetch_auction_activity(auction_id).await {
+            active_auctions.push(activity);
+          } else {
+            warn!("Failed to fetch activity of auction: {:?}", auction_id);
+          }
+        }
+
+        let mut auctions = old_auctions.clone();
+        let mut fetched_auctions = Vec::new();
+        for (auction, auction_proposals, auction_votes) in active_auctions {
+          fetched_ids.push(auction.id);
+          proposals.extend(auction_proposals);
+          votes.extend(auction_votes);
 
-      let mut new_auctions = Vec::new();
+          match auctions.iter_mut().find(|cached| cached.id == auction.id) {
+            Some(cached) => *cached = auction.clone(),
+            None => auctions.push(auction.clone()),
+          }
+          fetched_auctions.push(auction);
+        }
 
-      if let Some(old_auctions) = self
-        .cache
-        .get::<Vec<Auction>>("prop_house:auctions")
-        .await?
-      {
         let old_ids: Vec<_> = old_auctions.iter().map(|auction| &auction.id).collect();
-        new_auctions = auctions
+        let new_auctions: Vec<_> = fetched_auctions
           .iter()
           .filter(|auction| !old_ids.contains(&&auction.id))
           .cloned()
@@ -137,92 +472,353 @@ impl PropHouse {
             }
           }
         }
-      }
-
-      if !new_auctions.is_empty() {
-        self.cache.put("prop_house:auctions", &auctions).await;
-        info!("Updated auctions in cache");
-      }
-    } else {
-      warn!("Failed to fetch auctions");
-    }
 
-    if let Some(proposals) = self.fetcher.fetch_proposals().await {
-      debug!("Fetched {:?} proposals.", proposals.len());
-
-      let mut new_proposals = Vec::new();
-
-      if let Some(old_proposals) = self
-        .cache
-        .get::<Vec<Proposal>>("prop_house:proposals")
-        .await?
-      {
-        let old_ids: Vec<_> = old_proposals.iter().map(|proposal| &proposal.id).collect();
-        new_proposals = proposals
+        let changed_auctions: Vec<_> = fetched_auctions
           .iter()
-          .filter(|proposal| !old_ids.contains(&&proposal.id))
+          .filter(|auction| {
+            old_auctions
+              .iter()
+              .find(|old_auction| old_auction.id == auction.id)
+              .is_some_and(|old_auction| old_auction.status != auction.status)
+          })
           .cloned()
           .collect();
 
-        debug!("Found {:?} new proposals.", new_proposals.len());
+        debug!("Found {:?} changed auctions.", changed_auctions.len());
+
+        for auction in &changed_auctions {
+          // Auctions cached before statuses were tracked are only backfilled.
+          let was_tracked = old_auctions
+            .iter()
+            .any(|old_auction| old_auction.id == auction.id && old_auction.status.is_some());
+          if !was_tracked {
+            continue;
+          }
 
-        for proposal in &new_proposals {
-          info!("Handling a new proposal... ({:?})", proposal.id);
+          info!("Handling an auction status change... ({:?})", auction.id);
           for handler in &self.handlers {
-            if let Err(err) = handler.handle_new_proposal(proposal).await {
-              error!("Failed to handle new proposal: {:?}", err);
+            if let Err(err) = handler.handle_auction_status(auction).await {
+              error!("Failed to handle auction status change: {:?}", err);
             } else {
-              debug!("Successfully handled new proposal: {:?}", proposal.id);
+              debug!(
+                "Successfully handled auction status change: {:?}",
+                auction.id
+              );
             }
           }
+
+          if auction.status == Some(AuctionStatus::Closed) {
+            self.announce_winners(auction, &proposals).await;
+          }
         }
-      }
 
-      if !new_proposals.is_empty() {
-        self.cache.put("prop_house:proposals", &proposals).await;
-        info!("Updated proposals in cache");
+        if !new_auctions.is_empty() || !changed_auctions.is_empty() {
+          self
+            .cache
+            .put(&self.community.cache_key("auctions"), &auctions)
+            .await;
+          info!("Updated auctions in cache");
+        }
+
+        if let Err(err) = self.send_auction_reminders(&auctions).await {
+          error!("Failed to send auction reminders: {:?}", err);
+        }
+      } else {
+        warn!("Failed to fetch active auctions");
       }
-    } else {
-      warn!("Failed to fetch proposals");
     }
 
-    if let Some(votes) = self.fetcher.fetch_votes().await {
-      debug!("Fetched {:?} votes.", votes.len());
-
-      let mut new_votes = Vec::new();
+    debug!(
+      "Fetched {:?} proposals and {:?} votes from {:?} active auctions.",
+      proposals.len(),
+      votes.len(),
+      fetched_ids.len()
+    );
+
+    if let Some(old_proposals) = self
+      .cache
+      .get::<Vec<Proposal>>(&self.community.cache_key("proposals"))
+      .await?
+    {
+      let old_ids: Vec<_> = old_proposals.iter().map(|proposal| &proposal.id).collect();
+      let new_proposals: Vec<_> = proposals
+        .iter()
+        .filter(|proposal| !old_ids.contains(&&proposal.id))
+        .cloned()
+        .collect();
+
+      debug!("Found {:?} new proposals.", new_proposals.len());
+
+      for proposal in &new_proposals {
+        info!("Handling a new proposal... ({:?})", proposal.id);
+        for handler in &self.handlers {
+          if let Err(err) = handler.handle_new_proposal(proposal).await {
+            error!("Failed to handle new proposal: {:?}", err);
+          } else {
+            debug!("Successfully handled new proposal: {:?}", proposal.id);
+          }
+        }
+      }
 
-      if let Some(old_votes) = self.cache.get::<Vec<Vote>>("prop_house:votes").await? {
-        let old_ids: Vec<_> = old_votes.iter().map(|vote| &vote.id).collect();
-        new_votes = votes
-          .iter()
-          .filter(|vote| !old_ids.contains(&&vote.id))
-          .cloned()
+      if !new_proposals.is_empty() {
+        let proposals: Vec<_> = old_proposals
+          .into_iter()
+          .filter(|proposal| !fetched_ids.contains(&proposal.auction_id))
+          .chain(proposals.iter().cloned())
           .collect();
 
-        debug!("Found {:?} new votes.", new_votes.len());
+        self
+          .cache
+          .put(&self.community.cache_key("proposals"), &proposals)
+          .await;
+        info!("Updated proposals in cache");
+      }
+    }
 
-        for vote in &new_votes {
-          info!("Handling a new vote...");
-          for handler in &self.handlers {
-            if let Err(err) = handler.handle_new_vote(vote).await {
-              error!("Failed to handle new vote: {:?}", err);
-            } else {
-              debug!("Successfully handled new vote: {:?}", vote.id);
-            }
+    self.check_infinite_proposals().await?;
+
+    if let Some(old_votes) = self
+      .cache
+      .get::<Vec<Vote>>(&self.community.cache_key("votes"))
+      .await?
+    {
+      let old_ids: Vec<_> = old_votes.iter().map(|vote| &vote.id).collect();
+      let new_votes: Vec<_> = votes
+        .iter()
+        .filter(|vote| !old_ids.contains(&&vote.id))
+        .cloned()
+        .collect();
+
+      let votes: Vec<_> = old_votes
+        .iter()
+        .filter(|vote| !fetched_ids.contains(&vote.auction_id))
+        .chain(votes.iter())
+        .cloned()
+        .collect();
+
+      debug!("Found {:?} new votes.", new_votes.len());
+
+      for vote in &new_votes {
+        info!("Handling a new vote...");
+        let rank = vote.proposal_rank(&votes);
+        for handler in &self.handlers {
+          if let Err(err) = handler.handle_new_vote(vote, rank).await {
+            error!("Failed to handle new vote: {:?}", err);
+          } else {
+            debug!("Successfully handled new vote: {:?}", vote.id);
           }
         }
       }
 
       if !new_votes.is_empty() {
-        self.cache.put("prop_house:votes", &votes).await;
+        self
+          .cache
+          .put(&self.community.cache_key("votes"), &votes)
+          .await;
         info!("Updated votes in cache");
       }
-    } else {
-      warn!("Failed to fetch votes");
     }
 
     debug!("Start function finished.");
 
     Ok(())
   }
+
+  /// Announces new infinite round proposals and those reaching a quorum,
+  /// tracking the rest until their voting period ends.
+  async fn check_infinite_proposals(&self) -> Result<()> {
+    let cursor = self
+      .cache
+      .get::<String>(&self.community.cache_key("infinite_proposals:cursor"))
+      .await?
+      .unwrap_or_else(|| Utc::now().to_rfc3339());
+    let old_proposals = self
+      .cache
+      .get::<Vec<InfiniteProposal>>(&self.community.cache_key("infinite_proposals"))
+      .await?
+      .unwrap_or_default();
+
+    let Some(created_proposals) = self.fetcher.fetch_infinite_proposal_ids(&cursor).await else {
+      warn!("Failed to fetch infinite proposal ids");
+      return Ok(());
+    };
+
+    let new_ids: Vec<_> = created_proposals
+      .iter()
+      .map(|(id, _)| *id)
+      .filter(|id| !old_proposals.iter().any(|proposal| proposal.id == *id))
+      .collect();
+
+    debug!("Found {:?} new infinite proposals.", new_ids.len());
+
+    let ids: Vec<_> = old_proposals
+      .iter()
+      .map(|proposal| proposal.id)
+      .chain(new_ids.iter().copied())
+      .collect();
+    let proposals = self.fetcher.fetch_infinite_proposals(&ids).await;
+
+    let now = Utc::now().timestamp();
+    let mut tracked_proposals = Vec::new();
+
+    for proposal in &proposals {
+      if new_ids.contains(&proposal.id) {
+        info!("Handling a new infinite proposal... ({:?})", proposal.id);
+        for handler in &self.handlers {
+          if let Err(err) = handler.handle_new_infinite_proposal(proposal).await {
+            error!("Failed to handle new infinite proposal: {:?}", err);
+          } else {
+            debug!(
+              "Successfully handled new infinite proposal: {:?}",
+              proposal.id
+            );
+          }
+        }
+      }
+
+      if proposal.is_funded() || proposal.is_rejected() {
+        info!(
+          "Handling an infinite proposal result... ({:?})",
+          proposal.id
+        );
+        for handler in &self.handlers {
+          if let Err(err) = handler.handle_infinite_proposal_result(proposal).await {
+            error!("Failed to handle infinite proposal result: {:?}", err);
+          } else {
+            debug!(
+              "Successfully handled infinite proposal result: {:?}",
+              proposal.id
+            );
+          }
+        }
+      } else if now < proposal.voting_end {
+        tracked_proposals.push(proposal.clone());
+      }
+    }
+
+    let mut tracked_ids: Vec<_> = tracked_proposals
+      .iter()
+      .map(|proposal| proposal.id)
+      .collect();
+    let mut old_ids: Vec<_> = old_proposals.iter().map(|proposal| proposal.id).collect();
+    tracked_ids.sort();
+    old_ids.sort();
+
+    if tracked_ids != old_ids {
+      self
+        .cache
+        .put(
+          &self.community.cache_key("infinite_proposals"),
+          &tracked_proposals,
+        )
+        .await;
+      info!("Updated infinite proposals in cache");
+    }
+
+    let latest = created_proposals
+      .iter()
+      .filter_map(|(_, created)| DateTime::parse_from_rfc3339(created).ok())
+      .max();
+    if let Some(latest) = latest {
+      let cursor = (latest + Duration::milliseconds(1)).to_rfc3339();
+      self
+        .cache
+        .put(
+          &self.community.cache_key("infinite_proposals:cursor"),
+          &cursor,
+        )
+        .await;
+    }
+
+    Ok(())
+  }
+
+  /// Ranks the proposals of a closed auction by votes and announces its
+  /// winners.
+  async fn announce_winners(&self, auction: &Auction, proposals: &[Proposal]) {
+    let mut winners: Vec<_> = proposals
+      .iter()
+      .filter(|proposal| proposal.auction_id == auction.id && proposal.vote_count > 0)
+      .cloned()
+      .collect();
+
+    winners.sort_by_key(|proposal| Reverse(proposal.vote_count));
+    winners.truncate(auction.num_winners.try_into().unwrap_or_default());
+
+    if winners.is_empty() {
+      debug!("No winners found for auction: {:?}", auction.id);
+      return;
+    }
+
+    info!("Handling auction winners... ({:?})", auction.id);
+    for handler in &self.handlers {
+      if let Err(err) = handler.handle_auction_winners(auction, &winners).await {
+        error!("Failed to handle auction winners: {:?}", err);
+      } else {
+        debug!("Successfully handled auction winners: {:?}", auction.id);
+      }
+    }
+  }
+
+  /// Sends a reminder once per auction and stage when its deadline is near.
+  async fn send_auction_reminders(&self, auctions: &[Auction]) -> Result<()> {
+    let now = Utc::now().timestamp();
+
+    let old_reminders = self
+      .cache
+      .get::<HashMap<isize, Vec<AuctionStatus>>>(&self.community.cache_key("auctions:reminders"))
+      .await?
+      .unwrap_or_default();
+
+    let mut reminders: HashMap<isize, Vec<AuctionStatus>> = old_reminders
+      .iter()
+      .filter(|(id, _)| {
+        auctions.iter().any(|auction| {
+          auction.id == **id
+            && matches!(
+              auction.status,
+              Some(AuctionStatus::Open | AuctionStatus::Voting)
+            )
+        })
+      })
+      .map(|(id, stages)| (*id, stages.clone()))
+      .collect();
+
+    for auction in auctions {
+      let Some(stage @ (AuctionStatus::Open | AuctionStatus::Voting)) = auction.status else {
+        continue;
+      };
+      let Some(deadline) = auction.deadline(stage) else {
+        continue;
+      };
+      if now < deadline - AUCTION_REMINDER_PERIOD || now >= deadline {
+        continue;
+      }
+
+      let sent_stages = reminders.entry(auction.id).or_default();
+      if sent_stages.contains(&stage) {
+        continue;
+      }
+      sent_stages.push(stage);
+
+      info!("Handling an auction reminder... ({:?})", auction.id);
+      for handler in &self.handlers {
+        if let Err(err) = handler.handle_auction_reminder(auction, stage).await {
+          error!("Failed to handle auction reminder: {:?}", err);
+        } else {
+          debug!("Successfully handled auction reminder: {:?}", auction.id);
+        }
+      }
+    }
+
+    if reminders != old_reminders {
+      self
+        .cache
+        .put(&self.community.cache_key("auctions:reminders"), &reminders)
+        .await;
+      info!("Updated auction reminders in cache");
+    }
+
+    Ok(())
+  }
 }
@@ -1,11 +1,12 @@
 use std::convert::TryInto;
 
+use chrono::DateTime;
 use graphql_client::{reqwest::post_graphql, GraphQLQuery};
 use log::{debug, error};
 use reqwest::Client;
 use worker::{Env, Result};
 
-use crate::prop_lot::{Comment, Idea, Vote};
+use crate::prop_lot::{Comment, Idea, UserActivity, Vote};
 
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -34,8 +35,39 @@ struct VoteQuery;
 )]
 struct CommentQuery;
 
+#[derive(GraphQLQuery)]
+#[graphql(
+  schema_path = "graphql/schemas/prop_lot_schema.graphql",
+  query_path = "graphql/queries/prop_lot_query.graphql",
+  response_derives = "Clone",
+  deprecated = "warn"
+)]
+struct UserQuery;
+
 type Date = String;
 
+fn tag_type_name(tag_type: &idea_query::TagType) -> String {
+  use idea_query::TagType::*;
+
+  match tag_type {
+    NEW => "NEW",
+    INFO => "INFO",
+    ARCHIVED => "ARCHIVED",
+    DISCUSSION => "DISCUSSION",
+    PUBLIC_GOOD => "PUBLIC_GOOD",
+    GOVERNANCE => "GOVERNANCE",
+    COMMUNITY => "COMMUNITY",
+    CREATIVE => "CREATIVE",
+    OTHER => "OTHER",
+    CONSENSUS => "CONSENSUS",
+    CLOSED => "CLOSED",
+    SOFTWARE => "SOFTWARE",
+    HARDWARE => "HARDWARE",
+    Other(name) => name,
+  }
+  .to_string()
+}
+
 pub struct GraphQLFetcher {
   graphql_url: String,
 }
@@ -91,7 +123,34 @@ impl GraphQLFetcher {
         id: idea.id.try_into().unwrap(),
         title: idea.title.clone(),
         tldr: idea.tldr.clone(),
+        description: idea.description.clone(),
         creator_id: idea.creator_id.clone(),
+        created: DateTime::parse_from_rfc3339(&idea.created_at)
+          .map(|created| created.timestamp())
+          .unwrap_or_default(),
+        tags: idea
+          .tags
+          .iter()
+          .flatten()
+          .map(|tag| tag.label.clone())
+          .collect(),
+        tag_types: idea
+          .tags
+          .iter()
+          .flatten()
+          .map(|tag| tag_type_name(&tag.type_))
+          .collect(),
+        votecount: idea.votecount.try_into().unwrap(),
+        comment_count: idea
+          .idea_stats
+          .as_ref()
+          .and_then(|stats| stats.comments)
+          .unwrap_or_default()
+          .try_into()
+          .unwrap(),
+        closed: Some(idea.closed),
+        consensus: idea.consensus,
+        deleted: idea.deleted,
       })
       .collect();
 
@@ -147,9 +206,42 @@ impl GraphQLFetcher {
         idea_id: comment.idea_id.try_into().unwrap(),
         author_id: comment.author_id.clone(),
         body: comment.body.clone(),
+        parent_id: comment
+          .parent_id
+          .map(|parent_id| parent_id.try_into().unwrap()),
+        deleted: comment.deleted,
       })
       .collect();
 
     Some(comments)
   }
+
+  pub async fn fetch_user_activity(&self) -> Option<Vec<UserActivity>> {
+    let response = self.fetch::<UserQuery>(user_query::Variables).await?;
+
+    let users = response
+      .users
+      .as_ref()?
+      .iter()
+      .map(|user| UserActivity {
+        wallet: user.wallet.clone(),
+        votes: user
+          .user_stats
+          .as_ref()
+          .and_then(|stats| stats.total_votes)
+          .unwrap_or_default()
+          .try_into()
+          .unwrap(),
+        comments: user
+          .user_stats
+          .as_ref()
+          .and_then(|stats| stats.total_comments)
+          .unwrap_or_default()
+          .try_into()
+          .unwrap(),
+      })
+      .collect();
+
+    Some(users)
+  }
 }
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use chrono::Local;
 use log::{error, info};
@@ -7,11 +9,17 @@ use worker::{Env, Error, Result};
 
 use crate::{
   cache::Cache,
-  prop_lot::{handler::Handler, Comment, Idea, Vote},
-  utils::{ens::get_domain_name, get_explorer_address, get_short_address},
+  prop_lot::{handler::Handler, Channel, Comment, Idea, Leaderboard, Vote},
+  utils::{
+    ens::get_domain_name,
+    get_explorer_address,
+    get_short_address,
+    text::{strip_markdown, truncate_chars},
+  },
 };
 
 pub struct DiscordHandler {
+  channel: Channel,
   base_url: String,
   webhook_url: String,
   cache: Cache,
@@ -19,8 +27,15 @@ pub struct DiscordHandler {
 }
 
 impl DiscordHandler {
-  pub fn new(base_url: String, webhook_url: String, cache: Cache, client: Client) -> Self {
+  pub fn new(
+    channel: Channel,
+    base_url: String,
+    webhook_url: String,
+    cache: Cache,
+    client: Client,
+  ) -> Self {
     Self {
+      channel,
       base_url,
       webhook_url,
       cache,
@@ -28,26 +43,59 @@ impl DiscordHandler {
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<DiscordHandler> {
+  pub fn new_from_env(env: &Env, channel: &Channel) -> Result<DiscordHandler> {
     let base_url = env.var("PROP_LOT_BASE_URL")?.to_string();
-    let webhook_url = env.secret("PROP_LOT_DISCORD_WEBHOOK_URL")?.to_string();
+    let webhook_secret = channel
+      .discord_webhook_secret
+      .as_ref()
+      .ok_or("Discord webhook secret not configured")?;
+    let webhook_url = env.secret(webhook_secret)?.to_string();
 
     let cache = Cache::new_from_env(env);
     let client = Client::new();
 
-    Ok(Self::new(base_url, webhook_url, cache, client))
+    Ok(Self::new(
+      channel.clone(),
+      base_url,
+      webhook_url,
+      cache,
+      client,
+    ))
   }
 
-  async fn execute_webhook(&self, embed: Value) -> Result<()> {
+  /// Builds a webhook endpoint URL, keeping any query the webhook URL carries
+  /// (such as a thread id).
+  fn webhook_endpoint(&self, path: &str, query: &str) -> String {
+    let (base, webhook_query) = self
+      .webhook_url
+      .split_once('?')
+      .unwrap_or((&self.webhook_url, ""));
+
+    let query = [webhook_query, query]
+      .iter()
+      .filter(|part| !part.is_empty())
+      .copied()
+      .collect::<Vec<_>>()
+      .join("&");
+
+    if query.is_empty() {
+      format!("{}{}", base, path)
+    } else {
+      format!("{}{}?{}", base, path, query)
+    }
+  }
+
+  /// Posts the embed and returns the id of the created message.
+  async fn execute_webhook(&self, embed: Value) -> Result<String> {
     let msg_json = json!({
       "username": "Raven",
       "avatar_url": "https://i.imgur.com/qP2QpJq.png",
       "embeds": [embed]
     });
 
-    self
+    let response = self
       .client
-      .post(&self.webhook_url)
+      .post(self.webhook_endpoint("", "wait=true"))
       .header(header::CONTENT_TYPE, "application/json")
       .body(msg_json.to_string())
       .send()
@@ -57,27 +105,60 @@ impl DiscordHandler {
         Error::from(format!("Failed to execute webhook: {}", e))
       })?;
 
-    Ok(())
-  }
-}
+    let status = response.status();
+    let response_body = response.text().await.unwrap_or_default();
+    if !status.is_success() {
+      error!("Webhook responded with {}: {}", status, response_body);
+      return Err(Error::from(format!("Webhook responded with {}", status)));
+    }
 
-#[async_trait(? Send)]
-impl Handler for DiscordHandler {
-  async fn handle_new_idea(&self, idea: &Idea) -> Result<()> {
-    info!("Handling new idea: {}", idea.title);
+    // The message is posted either way, but can't be edited or deleted later
+    // without its id.
+    let message_id = serde_json::from_str::<Value>(&response_body)
+      .ok()
+      .and_then(|message| message["id"].as_str().map(str::to_string));
+    if message_id.is_none() {
+      error!(
+        "Failed to read message id from webhook response: {}",
+        response_body
+      );
+    }
+
+    Ok(message_id.unwrap_or_default())
+  }
 
+  /// Builds the new idea embed, with its current vote and comment counts.
+  async fn idea_embed(&self, idea: &Idea) -> Value {
     let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
     let url = format!("{}/idea/{}", self.base_url, idea.id);
+    let profile = format!("{}/profile/{}", self.base_url, idea.creator_id);
     let wallet = get_domain_name(&idea.creator_id)
       .await
       .unwrap_or(get_short_address(&idea.creator_id));
-    let explorer = get_explorer_address(&idea.creator_id);
-    let description = format!(
+
+    let mut description = format!(
       "{} created a new proposal on Prop Lot: “{}”",
       wallet, idea.title
     );
+    if !idea.tldr.is_empty() {
+      description = format!(
+        "{}\n\n**TL;DR:** {}",
+        description,
+        truncate_chars(&strip_markdown(&idea.tldr), 300)
+      );
+    }
+    if !idea.description.is_empty() {
+      description = format!(
+        "{}\n\n{}",
+        description,
+        truncate_chars(&strip_markdown(&idea.description), 500)
+      );
+    }
+    if let Some(tags) = idea.tags_line() {
+      description = format!("{}\n\n{}", description, tags);
+    }
 
-    let embed = json!({
+    json!({
       "title": "New Prop Lot Proposal",
       "description": description,
       "url": url,
@@ -85,11 +166,103 @@ impl Handler for DiscordHandler {
       "footer": {"text": date},
       "author": {
         "name": wallet,
-        "url": explorer,
-      }
-    });
+        "url": profile,
+      },
+      "fields": [
+        {"name": "Votes", "value": idea.votecount.to_string(), "inline": true},
+        {"name": "Comments", "value": idea.comment_count.to_string(), "inline": true}
+      ]
+    })
+  }
 
-    self.execute_webhook(embed).await?;
+  async fn edit_message(&self, message_id: &str, embed: Value) -> Result<()> {
+    let msg_json = json!({"embeds": [embed]});
+
+    self
+      .client
+      .patch(self.webhook_endpoint(&format!("/messages/{}", message_id), ""))
+      .header(header::CONTENT_TYPE, "application/json")
+      .body(msg_json.to_string())
+      .send()
+      .await
+      .map_err(|e| {
+        error!("Failed to edit webhook message: {}", e);
+        Error::from(format!("Failed to edit webhook message: {}", e))
+      })?;
+
+    Ok(())
+  }
+
+  async fn delete_message(&self, message_id: &str) -> Result<()> {
+    self
+      .client
+      .delete(self.webhook_endpoint(&format!("/messages/{}", message_id), ""))
+      .send()
+      .await
+      .map_err(|e| {
+        error!("Failed to delete webhook message: {}", e);
+        Error::from(format!("Failed to delete webhook message: {}", e))
+      })?;
+
+    Ok(())
+  }
+
+  async fn store_message(&self, key: &str, id: isize, message_id: String) -> Result<()> {
+    if message_id.is_empty() {
+      return Ok(());
+    }
+
+    let mut messages = self
+      .cache
+      .get::<HashMap<String, String>>(key)
+      .await?
+      .unwrap_or_default();
+
+    messages.insert(id.to_string(), message_id);
+
+    self.cache.put(key, &messages).await;
+
+    Ok(())
+  }
+
+  async fn remove_message(&self, key: &str, id: isize) -> Result<()> {
+    let mut messages = self
+      .cache
+      .get::<HashMap<String, String>>(key)
+      .await?
+      .unwrap_or_default();
+
+    let message_id = messages
+      .remove(&id.to_string())
+      .ok_or("Message id not found")?;
+
+    self.delete_message(&message_id).await?;
+
+    self.cache.put(key, &messages).await;
+
+    Ok(())
+  }
+}
+
+#[async_trait(? Send)]
+impl Handler for DiscordHandler {
+  fn channel(&self) -> &Channel {
+    &self.channel
+  }
+
+  async fn handle_new_idea(&self, idea: &Idea) -> Result<()> {
+    info!("Handling new idea: {}", idea.title);
+
+    let embed = self.idea_embed(idea).await;
+
+    let message_id = self.execute_webhook(embed).await?;
+    self
+      .store_message(
+        &self.channel.cache_key("ideas:messages"),
+        idea.id,
+        message_id,
+      )
+      .await?;
 
     Ok(())
   }
@@ -101,13 +274,13 @@ impl Handler for DiscordHandler {
       .cache
       .get::<Vec<Idea>>("prop_lot:ideas")
       .await?
-      .unwrap();
+      .unwrap_or_default();
 
     let idea = ideas
       .iter()
       .find(|&a| a.id == vote.idea_id)
-      .unwrap()
-      .clone();
+      .cloned()
+      .ok_or("Idea not found")?;
 
     let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
     let wallet = get_domain_name(&vote.voter_id)
@@ -143,19 +316,93 @@ impl Handler for DiscordHandler {
     Ok(())
   }
 
-  async fn handle_new_comment(&self, comment: &Comment) -> Result<()> {
+  async fn handle_idea_edited(&self, idea: &Idea, edits: &[String]) -> Result<()> {
+    info!("Handling edited idea: {}", idea.title);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let url = format!("{}/idea/{}", self.base_url, idea.id);
+    let mut description = format!(
+      "“{}” proposal has been edited: {}.",
+      idea.title,
+      edits.join("; ")
+    );
+    if !idea.tldr.is_empty() {
+      description = format!(
+        "{}\n\n**TL;DR:** {}",
+        description,
+        truncate_chars(&strip_markdown(&idea.tldr), 300)
+      );
+    }
+
+    let embed = json!({
+      "title": "Prop Lot Proposal Edited",
+      "description": description,
+      "url": url,
+      "color": 0xFFB911,
+      "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    // The original announcement is refreshed so it doesn't show stale text.
+    self.handle_idea_activity(idea).await
+  }
+
+  async fn handle_idea_activity(&self, idea: &Idea) -> Result<()> {
+    let messages = self
+      .cache
+      .get::<HashMap<String, String>>(&self.channel.cache_key("ideas:messages"))
+      .await?
+      .unwrap_or_default();
+
+    // Only ideas announced since their messages were tracked can be edited.
+    let Some(message_id) = messages.get(&idea.id.to_string()) else {
+      return Ok(());
+    };
+
+    info!("Handling idea activity: {}", idea.title);
+
+    let embed = self.idea_embed(idea).await;
+
+    self.edit_message(message_id, embed).await
+  }
+
+  async fn handle_vote_milestone(&self, idea: &Idea, milestone: isize, net: isize) -> Result<()> {
+    info!("Handling vote milestone: {}", idea.title);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let url = format!("{}/idea/{}", self.base_url, idea.id);
+    let description = format!(
+      "“{}” proposal has passed {} votes, now at {} net votes.",
+      idea.title, milestone, net
+    );
+
+    let embed = json!({
+      "title": "Prop Lot Proposal Milestone",
+      "description": description,
+      "url": url,
+      "color": 0xFFB911,
+      "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_new_comment(&self, comment: &Comment, parent: Option<&Comment>) -> Result<()> {
     info!("Handling new comment from address: {}", comment.author_id);
 
     let ideas = self
       .cache
       .get::<Vec<Idea>>("prop_lot:ideas")
       .await?
-      .unwrap();
+      .unwrap_or_default();
     let idea = ideas
       .iter()
       .find(|&a| a.id == comment.idea_id)
-      .unwrap()
-      .clone();
+      .cloned()
+      .ok_or("Idea not found")?;
 
     let url = format!("{}/idea/{}", self.base_url, idea.id);
     let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
@@ -165,12 +412,20 @@ impl Handler for DiscordHandler {
     let explorer = get_explorer_address(&comment.author_id);
 
     let mut description = format!("{} has commented on “{}” proposal.", wallet, idea.title);
-    let chars_limit = 320 - 10 - (description.len() + url.len());
-    let mut comment_body = comment.clone().body;
-    if comment_body.len() > chars_limit {
-      comment_body.truncate(chars_limit);
-      comment_body.push_str("...");
+    if let Some(parent) = parent {
+      let parent_wallet = get_domain_name(&parent.author_id)
+        .await
+        .unwrap_or(get_short_address(&parent.author_id));
+      let parent_body = truncate_chars(&strip_markdown(&parent.body), 100);
+      description = format!(
+        "{} has commented on “{}” proposal, replying to {}: ‘{}’",
+        wallet, idea.title, parent_wallet, parent_body
+      );
     }
+    let comment_body = truncate_chars(
+      &strip_markdown(&comment.body),
+      320_usize.saturating_sub(10 + description.chars().count()),
+    );
     description = format!("{}\n\n“{}”", description, comment_body);
 
     let embed = json!({
@@ -185,6 +440,186 @@ impl Handler for DiscordHandler {
       }
     });
 
+    let message_id = self.execute_webhook(embed).await?;
+    self
+      .store_message(
+        &self.channel.cache_key("comments:messages"),
+        comment.id,
+        message_id,
+      )
+      .await?;
+
+    Ok(())
+  }
+
+  async fn handle_idea_closed(&self, idea: &Idea) -> Result<()> {
+    info!("Handling closed idea: {}", idea.title);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let url = format!("{}/idea/{}", self.base_url, idea.id);
+    let description = format!(
+      "“{}” proposal has been closed with {} votes.",
+      idea.title, idea.votecount
+    );
+
+    let embed = json!({
+      "title": "Prop Lot Proposal Closed",
+      "description": description,
+      "url": url,
+      "color": 0xFFB911,
+      "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_idea_consensus(&self, idea: &Idea, threshold: f64) -> Result<()> {
+    info!("Handling idea consensus: {}", idea.title);
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let url = format!("{}/idea/{}", self.base_url, idea.id);
+    let description = format!(
+      "“{}” proposal has crossed {}% consensus with {} votes.",
+      idea.title, threshold, idea.votecount
+    );
+
+    let embed = json!({
+      "title": "Prop Lot Proposal Consensus",
+      "description": description,
+      "url": url,
+      "color": 0xFFB911,
+      "footer": {"text": date}
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_idea_deleted(&self, idea: &Idea) -> Result<()> {
+    info!("Handling deleted idea: {}", idea.title);
+
+    self
+      .remove_message(&self.channel.cache_key("ideas:messages"), idea.id)
+      .await
+  }
+
+  async fn handle_comment_edited(&self, comment: &Comment, edits: &[String]) -> Result<()> {
+    info!("Handling edited comment: {}", comment.id);
+
+    let ideas = self
+      .cache
+      .get::<Vec<Idea>>("prop_lot:ideas")
+      .await?
+      .unwrap_or_default();
+    let idea = ideas
+      .iter()
+      .find(|&a| a.id == comment.idea_id)
+      .ok_or("Idea not found")?;
+
+    let url = format!("{}/idea/{}", self.base_url, idea.id);
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let wallet = get_domain_name(&comment.author_id)
+      .await
+      .unwrap_or(get_short_address(&comment.author_id));
+    let explorer = get_explorer_address(&comment.author_id);
+
+    let description = format!(
+      "{} has edited their comment on “{}” proposal ({}).",
+      wallet,
+      idea.title,
+      edits.join("; ")
+    );
+    let comment_body = truncate_chars(
+      &strip_markdown(&comment.body),
+      320_usize.saturating_sub(10 + description.chars().count()),
+    );
+
+    let embed = json!({
+      "title": "Prop Lot Proposal Comment Edited",
+      "description": format!("{}\n\n“{}”", description, comment_body),
+      "url": url,
+      "color": 0xFFB911,
+      "footer": {"text": date},
+      "author": {
+          "name": wallet,
+          "url": explorer,
+      }
+    });
+
+    self.execute_webhook(embed).await?;
+
+    Ok(())
+  }
+
+  async fn handle_comment_deleted(&self, comment: &Comment) -> Result<()> {
+    info!("Handling deleted comment: {}", comment.id);
+
+    self
+      .remove_message(&self.channel.cache_key("comments:messages"), comment.id)
+      .await
+  }
+
+  async fn handle_leaderboard(&self, leaderboard: &Leaderboard) -> Result<()> {
+    info!("Handling leaderboard");
+
+    let date = Local::now().format("%m/%d/%Y %I:%M %p").to_string();
+    let mut sections = vec![format!(
+      "{} new proposals were created on Prop Lot this week.",
+      leaderboard.new_ideas
+    )];
+
+    if !leaderboard.top_ideas.is_empty() {
+      let lines: Vec<_> = leaderboard
+        .top_ideas
+        .iter()
+        .enumerate()
+        .map(|(index, (idea, votes))| {
+          format!(
+            "{}. [“{}”]({}/idea/{}) with {} votes",
+            index + 1,
+            idea.title,
+            self.base_url,
+            idea.id,
+            votes
+          )
+        })
+        .collect();
+      sections.push(format!("**Top proposals**\n{}", lines.join("\n")));
+    }
+
+    for (title, users, unit) in [
+      ("Most active voters", &leaderboard.top_voters, "votes"),
+      (
+        "Most active commenters",
+        &leaderboard.top_commenters,
+        "comments",
+      ),
+    ] {
+      if users.is_empty() {
+        continue;
+      }
+
+      let mut lines = Vec::new();
+      for (index, (wallet, count)) in users.iter().enumerate() {
+        let name = get_domain_name(wallet)
+          .await
+          .unwrap_or(get_short_address(wallet));
+        lines.push(format!("{}. {} with {} {}", index + 1, name, count, unit));
+      }
+      sections.push(format!("**{}**\n{}", title, lines.join("\n")));
+    }
+
+    let embed = json!({
+      "title": "Prop Lot Weekly Leaderboard",
+      "description": sections.join("\n\n"),
+      "url": self.base_url,
+      "color": 0xFFB911,
+      "footer": {"text": date}
+    });
+
     self.execute_webhook(embed).await?;
 
     Ok(())
@@ -13,12 +13,16 @@ use worker::{Env, Error, Result};
 
 use crate::{
   cache::Cache,
-  prop_lot::{handler::Handler, Comment, Idea, Vote},
+  prop_lot::{handler::Handler, Channel, Comment, Idea, Leaderboard, Vote},
   utils,
-  utils::ens::get_wallet_handle,
+  utils::{
+    ens::get_wallet_handle,
+    text::{strip_markdown, truncate_bytes},
+  },
 };
 
 pub(crate) struct FarcasterHandler {
+  channel: Channel,
   base_url: String,
   warpcast_url: String,
   bearer_token: String,
@@ -30,15 +34,18 @@ pub(crate) struct FarcasterHandler {
 
 impl FarcasterHandler {
   pub fn new(
+    channel: Channel,
     base_url: String,
     warpcast_url: String,
     bearer_token: String,
-    channel_key: String,
     cache: Cache,
     client: Client,
     link: Link,
   ) -> Self {
+    let channel_key = channel.warp_cast_channel.clone().unwrap_or_default();
+
     Self {
+      channel,
       base_url,
       warpcast_url,
       bearer_token,
@@ -49,27 +56,71 @@ impl FarcasterHandler {
     }
   }
 
-  pub fn new_from_env(env: &Env) -> Result<FarcasterHandler> {
+  pub fn new_from_env(env: &Env, channel: &Channel) -> Result<FarcasterHandler> {
     let base_url = env.var("PROP_LOT_BASE_URL")?.to_string();
     let warpcast_url = env.var("WARP_CAST_API_BASE_URL")?.to_string();
     let bearer_token = env.secret("PROP_LOT_WARP_CAST_TOKEN")?.to_string();
-    let channel_key = env.var("PROP_LOT_WARP_CAST_CHANNEL")?.to_string();
 
     let cache = Cache::new_from_env(env);
     let client = Client::new();
     let link = Link::new_from_env(&env);
 
     Ok(Self::new(
+      channel.clone(),
       base_url,
       warpcast_url,
       bearer_token,
-      channel_key,
       cache,
       client,
       link,
     ))
   }
 
+  async fn delete_cast(&self, cast_hash: &str) -> Result<()> {
+    let url = format!("{}/casts", self.warpcast_url);
+    let token = format!("Bearer {}", self.bearer_token);
+    let mut headers = HeaderMap::new();
+
+    let parsed_token =
+      HeaderValue::from_str(&token).map_err(|_| Error::from("Error while parsing token"))?;
+
+    headers.insert(AUTHORIZATION, parsed_token);
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    let response = self
+      .client
+      .delete(url)
+      .headers(headers)
+      .json(&json!({"castHash": cast_hash}))
+      .send()
+      .await
+      .map_err(|e| {
+        error!("Failed to delete cast: {}", e);
+        Error::from(format!("Failed to delete cast: {}", e))
+      })?;
+
+    debug!("Response status: {:?}", response.status());
+
+    Ok(())
+  }
+
+  async fn remove_cast(&self, key: &str, id: isize) -> Result<()> {
+    let mut casts = self
+      .cache
+      .get::<HashMap<String, String>>(key)
+      .await?
+      .unwrap_or_default();
+
+    let cast_hash = casts.remove(&id.to_string()).ok_or("Cast hash not found")?;
+
+    self.delete_cast(&cast_hash).await?;
+
+    self.cache.put(key, &casts).await;
+
+    Ok(())
+  }
+
   async fn make_http_request(&self, request_data: Value) -> Result<Response> {
     let url = format!("{}/casts", self.warpcast_url);
     let token = format!("Bearer {}", self.bearer_token);
@@ -103,6 +154,10 @@ impl FarcasterHandler {
 
 #[async_trait(? Send)]
 impl Handler for FarcasterHandler {
+  fn channel(&self) -> &Channel {
+    &self.channel
+  }
+
   async fn handle_new_idea(&self, idea: &Idea) -> Result<()> {
     info!("Handling new idea: {}", idea.title);
 
@@ -114,10 +169,24 @@ impl Handler for FarcasterHandler {
 
     let wallet = get_wallet_handle(&idea.creator_id, "xyz.farcaster").await;
 
-    let description = format!(
+    let mut description = format!(
       "{} created a new proposal on Prop Lot: “{}”",
       wallet, idea.title
     );
+    let tags = idea
+      .tags_line()
+      .map(|tags| format!("\n\n{}", tags))
+      .unwrap_or_default();
+    if !idea.tldr.is_empty() {
+      let tldr = truncate_bytes(
+        &strip_markdown(&idea.tldr),
+        320_usize.saturating_sub(10 + description.len() + tags.len()),
+      );
+      if !tldr.is_empty() {
+        description = format!("{}\n\nTL;DR: {}", description, tldr);
+      }
+    }
+    description = format!("{}{}", description, tags);
 
     let request_data = json!({
       "text": description,
@@ -127,7 +196,7 @@ impl Handler for FarcasterHandler {
 
     let response = self.make_http_request(request_data).await.map_err(|e| {
       error!("Failed to make HTTP request: {}", e);
-      return e;
+      e
     })?;
 
     let response_body = response.text().await.map_err(|e| {
@@ -152,13 +221,16 @@ impl Handler for FarcasterHandler {
     let idea_id = idea.id;
     let mut ideas_casts = self
       .cache
-      .get::<HashMap<String, String>>("prop_lot:ideas:casts")
+      .get::<HashMap<String, String>>(&self.channel.cache_key("ideas:casts"))
       .await?
       .unwrap_or_default();
 
     ideas_casts.insert(idea_id.to_string(), cast_hash.to_string());
 
-    self.cache.put("prop_lot:ideas:casts", &ideas_casts).await;
+    self
+      .cache
+      .put(&self.channel.cache_key("ideas:casts"), &ideas_casts)
+      .await;
 
     Ok(())
   }
@@ -175,16 +247,17 @@ impl Handler for FarcasterHandler {
     let idea = ideas
       .iter()
       .find(|&a| a.id == vote.idea_id)
-      .clone()
       .ok_or("Idea not found in the funding list.")?;
 
     let ideas_casts = self
       .cache
-      .get::<HashMap<isize, String>>("prop_lot:ideas:casts")
+      .get::<HashMap<String, String>>(&self.channel.cache_key("ideas:casts"))
       .await?
       .unwrap_or_default();
 
-    let cast_hash = ideas_casts.get(&idea.id).ok_or("Cast hash not found")?;
+    let cast_hash = ideas_casts
+      .get(&idea.id.to_string())
+      .ok_or("Cast hash not found")?;
 
     let wallet = get_wallet_handle(&vote.voter_id, "xyz.farcaster").await;
 
@@ -220,40 +293,113 @@ impl Handler for FarcasterHandler {
     Ok(())
   }
 
-  async fn handle_new_comment(&self, comment: &Comment) -> Result<()> {
+  async fn handle_idea_edited(&self, idea: &Idea, edits: &[String]) -> Result<()> {
+    info!("Handling edited idea: {}", idea.title);
+
+    let ideas_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.channel.cache_key("ideas:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let cast_hash = ideas_casts
+      .get(&idea.id.to_string())
+      .ok_or("Cast hash not found")?;
+
+    let description = truncate_bytes(
+      &format!(
+        "“{}” proposal has been edited: {}.",
+        idea.title,
+        edits.join("; ")
+      ),
+      320,
+    );
+
+    let request_data = json!({
+      "text": description,
+      "channelKey": self.channel_key,
+      "parent": {"hash": cast_hash},
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_idea_activity(&self, _idea: &Idea) -> Result<()> {
+    // Casts cannot be edited, counts are only shown on Discord.
+    Ok(())
+  }
+
+  async fn handle_vote_milestone(&self, idea: &Idea, milestone: isize, net: isize) -> Result<()> {
+    info!("Handling vote milestone: {}", idea.title);
+
+    let ideas_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.channel.cache_key("ideas:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let cast_hash = ideas_casts
+      .get(&idea.id.to_string())
+      .ok_or("Cast hash not found")?;
+
+    let description = format!(
+      "“{}” proposal has passed {} votes, now at {} net votes.",
+      idea.title, milestone, net
+    );
+
+    let request_data = json!({
+      "text": description,
+      "channelKey": self.channel_key,
+      "parent": {"hash": cast_hash},
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_new_comment(&self, comment: &Comment, parent: Option<&Comment>) -> Result<()> {
     info!("Handling new comment from address: {}", comment.author_id);
 
     let ideas = self
       .cache
       .get::<Vec<Idea>>("prop_lot:ideas")
       .await?
-      .unwrap();
+      .unwrap_or_default();
 
     let idea = ideas
       .iter()
       .find(|&a| a.id == comment.idea_id)
-      .clone()
       .ok_or("Idea not found in the funding list.")?;
 
     let ideas_casts = self
       .cache
-      .get::<HashMap<String, String>>("prop_lot:ideas:casts")
+      .get::<HashMap<String, String>>(&self.channel.cache_key("ideas:casts"))
       .await?
       .unwrap_or_default();
 
-    let cast_hash = ideas_casts
-      .get(&idea.id.to_string())
-      .ok_or("Cast hash not found")?;
+    let mut comments_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.channel.cache_key("comments:casts"))
+      .await?
+      .unwrap_or_default();
+
+    // Replies are threaded under their parent comment's cast when there is one.
+    let cast_hash = parent
+      .and_then(|parent| comments_casts.get(&parent.id.to_string()))
+      .or_else(|| ideas_casts.get(&idea.id.to_string()))
+      .ok_or("Cast hash not found")?
+      .clone();
 
     let wallet = get_wallet_handle(&comment.author_id, "xyz.farcaster").await;
 
     let mut description = format!("{} has commented on “{}” proposal.", wallet, idea.title);
-    let chars_limit = 320 - 10 - description.len();
-    let mut comment_body = comment.clone().body;
-    if comment_body.len() > chars_limit {
-      comment_body.truncate(chars_limit);
-      comment_body.push_str("...");
-    }
+    let comment_body = truncate_bytes(
+      &strip_markdown(&comment.body),
+      320_usize.saturating_sub(10 + description.len()),
+    );
     description = format!("{}\n\n“{}”", description, comment_body);
 
     let request_data = json!({
@@ -262,6 +408,192 @@ impl Handler for FarcasterHandler {
       "parent": {"hash": cast_hash},
     });
 
+    let response = self.make_http_request(request_data).await.map_err(|e| {
+      error!("Failed to make HTTP request: {}", e);
+      e
+    })?;
+
+    let response_body = response.text().await.map_err(|e| {
+      error!("Failed to get text from response: {}", e);
+      Error::from(format!("Failed to get text from response: {}", e))
+    })?;
+
+    let parsed_body: serde_json::Result<Value> = serde_json::from_str(&response_body);
+
+    let response_body: Value = match parsed_body {
+      Ok(body) => body,
+      Err(e) => {
+        error!("Failed to parse JSON: {}", e);
+        return Err(e.into());
+      }
+    };
+
+    let comment_cast_hash = response_body["result"]["cast"]["hash"]
+      .as_str()
+      .unwrap_or_default();
+
+    comments_casts.insert(comment.id.to_string(), comment_cast_hash.to_string());
+
+    self
+      .cache
+      .put(&self.channel.cache_key("comments:casts"), &comments_casts)
+      .await;
+
+    Ok(())
+  }
+
+  async fn handle_idea_closed(&self, idea: &Idea) -> Result<()> {
+    info!("Handling closed idea: {}", idea.title);
+
+    let ideas_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.channel.cache_key("ideas:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let cast_hash = ideas_casts
+      .get(&idea.id.to_string())
+      .ok_or("Cast hash not found")?;
+
+    let description = format!(
+      "“{}” proposal has been closed with {} votes.",
+      idea.title, idea.votecount
+    );
+
+    let request_data = json!({
+      "text": description,
+      "channelKey": self.channel_key,
+      "parent": {"hash": cast_hash},
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_idea_consensus(&self, idea: &Idea, threshold: f64) -> Result<()> {
+    info!("Handling idea consensus: {}", idea.title);
+
+    let ideas_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.channel.cache_key("ideas:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let cast_hash = ideas_casts
+      .get(&idea.id.to_string())
+      .ok_or("Cast hash not found")?;
+
+    let description = format!(
+      "“{}” proposal has crossed {}% consensus with {} votes.",
+      idea.title, threshold, idea.votecount
+    );
+
+    let request_data = json!({
+      "text": description,
+      "channelKey": self.channel_key,
+      "parent": {"hash": cast_hash},
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_idea_deleted(&self, idea: &Idea) -> Result<()> {
+    info!("Handling deleted idea: {}", idea.title);
+
+    self
+      .remove_cast(&self.channel.cache_key("ideas:casts"), idea.id)
+      .await
+  }
+
+  async fn handle_comment_edited(&self, comment: &Comment, edits: &[String]) -> Result<()> {
+    info!("Handling edited comment: {}", comment.id);
+
+    let comments_casts = self
+      .cache
+      .get::<HashMap<String, String>>(&self.channel.cache_key("comments:casts"))
+      .await?
+      .unwrap_or_default();
+
+    let cast_hash = comments_casts
+      .get(&comment.id.to_string())
+      .ok_or("Cast hash not found")?;
+
+    let wallet = get_wallet_handle(&comment.author_id, "xyz.farcaster").await;
+
+    let description = format!(
+      "{} has edited their comment ({}).",
+      wallet,
+      edits.join("; ")
+    );
+    let comment_body = truncate_bytes(
+      &strip_markdown(&comment.body),
+      320_usize.saturating_sub(10 + description.len()),
+    );
+
+    let request_data = json!({
+      "text": format!("{}\n\n“{}”", description, comment_body),
+      "channelKey": self.channel_key,
+      "parent": {"hash": cast_hash},
+    });
+
+    self.make_http_request(request_data).await?;
+
+    Ok(())
+  }
+
+  async fn handle_comment_deleted(&self, comment: &Comment) -> Result<()> {
+    info!("Handling deleted comment: {}", comment.id);
+
+    self
+      .remove_cast(&self.channel.cache_key("comments:casts"), comment.id)
+      .await
+  }
+
+  async fn handle_leaderboard(&self, leaderboard: &Leaderboard) -> Result<()> {
+    info!("Handling leaderboard");
+
+    let mut sections = vec![format!(
+      "This week on Prop Lot: {} new proposals.",
+      leaderboard.new_ideas
+    )];
+
+    if !leaderboard.top_ideas.is_empty() {
+      let lines: Vec<_> = leaderboard
+        .top_ideas
+        .iter()
+        .enumerate()
+        .map(|(index, (idea, votes))| format!("{}. “{}” +{}", index + 1, idea.title, votes))
+        .collect();
+      sections.push(format!("Top proposals:\n{}", lines.join("\n")));
+    }
+
+    for (title, users) in [
+      ("Top voters", &leaderboard.top_voters),
+      ("Top commenters", &leaderboard.top_commenters),
+    ] {
+      if users.is_empty() {
+        continue;
+      }
+
+      let mut names = Vec::new();
+      for (wallet, count) in users {
+        let wallet = get_wallet_handle(wallet, "xyz.farcaster").await;
+        names.push(format!("{} ({})", wallet, count));
+      }
+      sections.push(format!("{}: {}", title, names.join(", ")));
+    }
+
+    let description = truncate_bytes(&sections.join("\n\n"), 320);
+
+    let request_data = json!({
+      "text": description,
+      "embeds": [self.base_url],
+      "channelKey": self.channel_key
+    });
+
     self.make_http_request(request_data).await?;
 
     Ok(())
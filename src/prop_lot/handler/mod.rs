@@ -1,14 +1,24 @@
 use async_trait::async_trait;
 use worker::Result;
 
-use crate::prop_lot::{Comment, Idea, Vote};
+use crate::prop_lot::{Channel, Comment, Idea, Leaderboard, Vote};
 
 pub(crate) mod discord;
 pub(crate) mod farcaster;
 
 #[async_trait(? Send)]
 pub trait Handler {
+  fn channel(&self) -> &Channel;
   async fn handle_new_idea(&self, idea: &Idea) -> Result<()>;
   async fn handle_new_vote(&self, vote: &Vote) -> Result<()>;
-  async fn handle_new_comment(&self, comment: &Comment) -> Result<()>;
+  async fn handle_idea_edited(&self, idea: &Idea, edits: &[String]) -> Result<()>;
+  async fn handle_idea_activity(&self, idea: &Idea) -> Result<()>;
+  async fn handle_vote_milestone(&self, idea: &Idea, milestone: isize, net: isize) -> Result<()>;
+  async fn handle_idea_closed(&self, idea: &Idea) -> Result<()>;
+  async fn handle_idea_consensus(&self, idea: &Idea, threshold: f64) -> Result<()>;
+  async fn handle_idea_deleted(&self, idea: &Idea) -> Result<()>;
+  async fn handle_comment_edited(&self, comment: &Comment, edits: &[String]) -> Result<()>;
+  async fn handle_comment_deleted(&self, comment: &Comment) -> Result<()>;
+  async fn handle_leaderboard(&self, leaderboard: &Leaderboard) -> Result<()>;
+  async fn handle_new_comment(&self, comment: &Comment, parent: Option<&Comment>) -> Result<()>;
 }
@@ -1,6 +1,9 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use chrono::Utc;
 use log::{debug, error, info, warn};
-use serde::{Deserialize, Serialize};
-use worker::{Env, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use worker::{Env, Error, Result};
 
 use crate::{
   cache::Cache,
@@ -8,6 +11,7 @@ use crate::{
     fetcher::GraphQLFetcher,
     handler::{discord::DiscordHandler, farcaster::FarcasterHandler, Handler},
   },
+  utils::text::summarize_diff,
 };
 
 pub(crate) mod fetcher;
@@ -18,7 +22,133 @@ pub struct Idea {
   pub id: isize,
   pub title: String,
   pub tldr: String,
+  #[serde(default)]
+  pub description: String,
   pub creator_id: String,
+  /// Unix timestamp, left at zero on ideas cached before it was tracked.
+  #[serde(default)]
+  pub created: i64,
+  #[serde(default)]
+  pub tags: Vec<String>,
+  /// Tag types, such as `SOFTWARE`, used to route the idea to channels.
+  #[serde(default)]
+  pub tag_types: Vec<String>,
+  #[serde(default)]
+  pub votecount: isize,
+  #[serde(default)]
+  pub comment_count: isize,
+  /// Left empty on ideas cached before the closed state was tracked.
+  #[serde(default)]
+  pub closed: Option<bool>,
+  /// Share of the voting weight in favor of the idea, as a percentage.
+  #[serde(default)]
+  pub consensus: Option<f64>,
+  #[serde(default)]
+  pub deleted: bool,
+}
+
+impl Idea {
+  /// Describes the edits made to the idea title, TL;DR and description since
+  /// the given version of the idea.
+  pub(crate) fn edits_since(&self, old: &Idea) -> Vec<String> {
+    let mut edits = Vec::new();
+
+    if old.title != self.title {
+      edits.push(format!(
+        "title changed from “{}” to “{}”",
+        old.title, self.title
+      ));
+    }
+
+    if old.tldr != self.tldr {
+      edits.push(format!(
+        "TL;DR edited, {}",
+        summarize_diff(&old.tldr, &self.tldr)
+      ));
+    }
+
+    // Ideas cached before descriptions were tracked have none to compare.
+    if !old.description.is_empty() && old.description != self.description {
+      edits.push(format!(
+        "description edited, {}",
+        summarize_diff(&old.description, &self.description)
+      ));
+    }
+
+    edits
+  }
+
+  /// Formats the idea tags as a single line, if it has any.
+  pub(crate) fn tags_line(&self) -> Option<String> {
+    if self.tags.is_empty() {
+      None
+    } else {
+      Some(format!("Tags: {}", self.tags.join(", ")))
+    }
+  }
+}
+
+impl Comment {
+  /// Describes the edits made to the comment body since the given version of
+  /// the comment.
+  pub(crate) fn edits_since(&self, old: &Comment) -> Vec<String> {
+    if old.body == self.body {
+      vec![]
+    } else {
+      vec![summarize_diff(&old.body, &self.body)]
+    }
+  }
+}
+
+/// A place announcements are sent to, optionally limited to ideas with some
+/// tag types.
+#[derive(Deserialize, Clone)]
+pub struct Channel {
+  pub(crate) id: String,
+  /// Tag types followed by the channel. Ideas without any followed tag go to
+  /// the channels without tags.
+  #[serde(default)]
+  pub(crate) tags: Vec<String>,
+  /// Name of the secret holding the channel Discord webhook URL.
+  #[serde(default)]
+  pub(crate) discord_webhook_secret: Option<String>,
+  #[serde(default)]
+  pub(crate) warp_cast_channel: Option<String>,
+  #[serde(default)]
+  pub(crate) cache_namespace: Option<String>,
+}
+
+impl Channel {
+  /// Reads the default channel from the `PROP_LOT_*` variables, followed by the
+  /// tag channels of `PROP_LOT_CHANNELS`, a JSON array.
+  pub fn list_from_env(env: &Env) -> Result<Vec<Channel>> {
+    let mut channels = vec![Channel {
+      id: "default".to_string(),
+      tags: vec![],
+      discord_webhook_secret: Some("PROP_LOT_DISCORD_WEBHOOK_URL".to_string()),
+      warp_cast_channel: Some(env.var("PROP_LOT_WARP_CAST_CHANNEL")?.to_string()),
+      cache_namespace: Some("prop_lot".to_string()),
+    }];
+
+    if let Ok(tag_channels) = env.var("PROP_LOT_CHANNELS") {
+      let tag_channels: Vec<Channel> = serde_json::from_str(&tag_channels.to_string())
+        .map_err(|e| Error::from(format!("Failed to parse PROP_LOT_CHANNELS: {}", e)))?;
+      channels.extend(tag_channels);
+    }
+
+    Ok(channels)
+  }
+
+  pub(crate) fn follows(&self, idea: &Idea) -> bool {
+    idea.tag_types.iter().any(|tag| self.tags.contains(tag))
+  }
+
+  pub(crate) fn cache_key(&self, key: &str) -> String {
+    match &self.cache_namespace {
+      Some(namespace) => format!("{}:{}", namespace, key),
+      None => format!("prop_lot:{}:{}", self.id, key),
+    }
+  }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -36,20 +166,155 @@ pub struct Comment {
   pub idea_id: isize,
   pub author_id: String,
   pub body: String,
+  #[serde(default)]
+  pub parent_id: Option<isize>,
+  #[serde(default)]
+  pub deleted: bool,
+}
+
+/// Sums the weighted votes of each idea, counting votes against negatively.
+fn net_votes(votes: &[Vote]) -> HashMap<isize, isize> {
+  let mut totals = HashMap::new();
+  for vote in votes {
+    *totals.entry(vote.idea_id).or_default() += vote.voter_weight * vote.direction;
+  }
+  totals
+}
+
+/// Time between two leaderboards.
+const LEADERBOARD_PERIOD: i64 = 7 * 24 * 60 * 60;
+const LEADERBOARD_SIZE: usize = 3;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UserActivity {
+  pub wallet: String,
+  pub votes: isize,
+  pub comments: isize,
+}
+
+/// Totals recorded when the previous leaderboard was posted, so the next one
+/// only counts the activity since then.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LeaderboardSnapshot {
+  pub created: i64,
+  pub idea_votes: HashMap<String, isize>,
+  /// Activity by wallet, also read from the list older snapshots recorded.
+  #[serde(deserialize_with = "deserialize_user_activity")]
+  pub user_activity: HashMap<String, UserActivity>,
+}
+
+fn deserialize_user_activity<'de, D>(
+  deserializer: D,
+) -> std::result::Result<HashMap<String, UserActivity>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum Activity {
+    ByWallet(HashMap<String, UserActivity>),
+    List(Vec<UserActivity>),
+  }
+
+  Ok(match Activity::deserialize(deserializer)? {
+    Activity::ByWallet(user_activity) => user_activity,
+    Activity::List(user_activity) => user_activity
+      .into_iter()
+      .map(|user| (user.wallet.clone(), user))
+      .collect(),
+  })
+}
+
+pub struct Leaderboard {
+  pub new_ideas: usize,
+  pub top_ideas: Vec<(Idea, isize)>,
+  pub top_voters: Vec<(String, isize)>,
+  pub top_commenters: Vec<(String, isize)>,
+}
+
+impl Leaderboard {
+  /// Compares the current totals with those of the previous leaderboard.
+  pub(crate) fn since(
+    snapshot: &LeaderboardSnapshot,
+    ideas: &[Idea],
+    user_activity: &[UserActivity],
+  ) -> Self {
+    let new_ideas = ideas
+      .iter()
+      .filter(|idea| !idea.deleted && !snapshot.idea_votes.contains_key(&idea.id.to_string()))
+      .count();
+
+    let mut top_ideas: Vec<_> = ideas
+      .iter()
+      .filter(|idea| !idea.deleted)
+      .map(|idea| {
+        let previous = snapshot
+          .idea_votes
+          .get(&idea.id.to_string())
+          .copied()
+          .unwrap_or_default();
+        (idea.clone(), idea.votecount - previous)
+      })
+      .filter(|(_, votes)| *votes > 0)
+      .collect();
+    top_ideas.sort_by_key(|(_, votes)| Reverse(*votes));
+    top_ideas.truncate(LEADERBOARD_SIZE);
+
+    let activity_since = |count: fn(&UserActivity) -> isize| {
+      let mut users: Vec<_> = user_activity
+        .iter()
+        .map(|user| {
+          let previous = snapshot
+            .user_activity
+            .get(&user.wallet)
+            .map(count)
+            .unwrap_or_default();
+          (user.wallet.clone(), count(user) - previous)
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect();
+      users.sort_by_key(|(_, count)| Reverse(*count));
+      users.truncate(LEADERBOARD_SIZE);
+      users
+    };
+
+    Self {
+      new_ideas,
+      top_ideas,
+      top_voters: activity_since(|user| user.votes),
+      top_commenters: activity_since(|user| user.comments),
+    }
+  }
 }
 
 pub struct PropLot {
   cache: Cache,
   fetcher: GraphQLFetcher,
   handlers: Vec<Box<dyn Handler>>,
+  consensus_threshold: Option<f64>,
+  vote_messages_enabled: bool,
+  vote_milestones: Vec<isize>,
+  leaderboard_enabled: bool,
 }
 
 impl PropLot {
-  pub fn new(cache: Cache, fetcher: GraphQLFetcher, handlers: Vec<Box<dyn Handler>>) -> Self {
+  pub fn new(
+    cache: Cache,
+    fetcher: GraphQLFetcher,
+    handlers: Vec<Box<dyn Handler>>,
+    consensus_threshold: Option<f64>,
+    vote_messages_enabled: bool,
+    vote_milestones: Vec<isize>,
+    leaderboard_enabled: bool,
+  ) -> Self {
     Self {
       cache,
       fetcher,
       handlers,
+      consensus_threshold,
+      vote_messages_enabled,
+      vote_milestones,
+      leaderboard_enabled,
     }
   }
 
@@ -58,17 +323,102 @@ impl PropLot {
     let fetcher = GraphQLFetcher::new_from_env(env)?;
     let mut handlers = vec![];
 
-    if env.var("PROP_LOT_DISCORD_ENABLED").unwrap().to_string() == "true" {
-      let discord_handler: Box<dyn Handler> = Box::new(DiscordHandler::new_from_env(env)?);
-      handlers.push(discord_handler);
+    for channel in Channel::list_from_env(env)? {
+      if env.var("PROP_LOT_DISCORD_ENABLED").unwrap().to_string() == "true"
+        && channel.discord_webhook_secret.is_some()
+      {
+        let discord_handler: Box<dyn Handler> =
+          Box::new(DiscordHandler::new_from_env(env, &channel)?);
+        handlers.push(discord_handler);
+      }
+
+      if env.var("PROP_LOT_FARCASTER_ENABLED").unwrap().to_string() == "true"
+        && channel.warp_cast_channel.is_some()
+      {
+        let farcaster_handler: Box<dyn Handler> =
+          Box::new(FarcasterHandler::new_from_env(env, &channel)?);
+        handlers.push(farcaster_handler);
+      }
     }
 
-    if env.var("PROP_LOT_FARCASTER_ENABLED").unwrap().to_string() == "true" {
-      let farcaster_handler: Box<dyn Handler> = Box::new(FarcasterHandler::new_from_env(env)?);
-      handlers.push(farcaster_handler);
+    let consensus_threshold = env
+      .var("PROP_LOT_CONSENSUS_THRESHOLD")
+      .ok()
+      .and_then(|value| value.to_string().parse::<f64>().ok());
+
+    let vote_messages_enabled = env
+      .var("PROP_LOT_VOTE_MESSAGES_ENABLED")
+      .map(|value| value.to_string() == "true")
+      .unwrap_or(true);
+
+    let vote_milestones = env
+      .var("PROP_LOT_VOTE_MILESTONES")
+      .map(|value| {
+        value
+          .to_string()
+          .split(',')
+          .filter_map(|milestone| milestone.trim().parse::<isize>().ok())
+          .collect()
+      })
+      .unwrap_or_default();
+
+    let leaderboard_enabled = env
+      .var("PROP_LOT_LEADERBOARD_ENABLED")
+      .map(|value| value.to_string() == "true")
+      .unwrap_or_default();
+
+    Ok(Self::new(
+      cache,
+      fetcher,
+      handlers,
+      consensus_threshold,
+      vote_messages_enabled,
+      vote_milestones,
+      leaderboard_enabled,
+    ))
+  }
+
+  /// Picks the channels following the idea tags, falling back to the channels
+  /// without tags.
+  fn route(&self, idea: Option<&Idea>) -> Vec<String> {
+    let channel_ids = |follows: &dyn Fn(&Channel) -> bool| {
+      let mut ids: Vec<String> = self
+        .handlers
+        .iter()
+        .map(|handler| handler.channel())
+        .filter(|channel| follows(channel))
+        .map(|channel| channel.id.clone())
+        .collect();
+      ids.dedup();
+      ids
+    };
+
+    let routed = channel_ids(&|channel| idea.is_some_and(|idea| channel.follows(idea)));
+    if !routed.is_empty() {
+      return routed;
     }
 
-    Ok(Self::new(cache, fetcher, handlers))
+    channel_ids(&|channel| channel.tags.is_empty())
+  }
+
+  /// Picks the handlers of the channels the idea was first announced to, so
+  /// its thread stays in place when its tags change.
+  fn handlers_for(
+    &self,
+    routes: &HashMap<String, Vec<String>>,
+    idea: Option<&Idea>,
+  ) -> Vec<&dyn Handler> {
+    let route = idea
+      .and_then(|idea| routes.get(&idea.id.to_string()))
+      .cloned()
+      .unwrap_or_else(|| self.route(idea));
+
+    self
+      .handlers
+      .iter()
+      .filter(|handler| route.contains(&handler.channel().id))
+      .map(|handler| handler.as_ref())
+      .collect()
   }
 
   pub async fn setup(&self) {
@@ -112,16 +462,24 @@ impl PropLot {
 
     debug!("Start function started.");
 
+    let mut routes = self
+      .cache
+      .get::<HashMap<String, Vec<String>>>("prop_lot:ideas:channels")
+      .await?
+      .unwrap_or_default();
+
     if let Some(ideas) = self.fetcher.fetch_ideas().await {
       debug!("Fetched {:?} ideas.", ideas.len());
 
       let mut new_ideas = Vec::new();
+      let mut changed_ideas = Vec::new();
+      let mut deleted_ideas = Vec::new();
 
       if let Some(old_ideas) = self.cache.get::<Vec<Idea>>("prop_lot:ideas").await? {
         let old_ids: Vec<_> = old_ideas.iter().map(|idea| &idea.id).collect();
         new_ideas = ideas
           .iter()
-          .filter(|idea| !old_ids.contains(&&idea.id))
+          .filter(|idea| !old_ids.contains(&&idea.id) && !idea.deleted)
           .cloned()
           .collect();
 
@@ -129,7 +487,8 @@ impl PropLot {
 
         for idea in &new_ideas {
           info!("Handle a new idea...");
-          for handler in &self.handlers {
+          routes.insert(idea.id.to_string(), self.route(Some(idea)));
+          for handler in self.handlers_for(&routes, Some(idea)) {
             if let Err(err) = handler.handle_new_idea(idea).await {
               error!("Failed to handle new idea: {:?}", err);
             } else {
@@ -137,9 +496,118 @@ impl PropLot {
             }
           }
         }
+
+        changed_ideas = ideas
+          .iter()
+          .filter_map(|idea| {
+            let old_idea = old_ideas.iter().find(|old_idea| old_idea.id == idea.id)?;
+            let edits = idea.edits_since(old_idea);
+            let changed = old_idea.closed != idea.closed
+              || old_idea.consensus != idea.consensus
+              || old_idea.deleted != idea.deleted
+              || !edits.is_empty()
+              || old_idea.votecount != idea.votecount
+              || old_idea.comment_count != idea.comment_count;
+            changed.then(|| (idea.clone(), edits))
+          })
+          .collect();
+
+        debug!("Found {:?} changed ideas.", changed_ideas.len());
+
+        for (idea, edits) in &changed_ideas {
+          let Some(old_idea) = old_ideas.iter().find(|old_idea| old_idea.id == idea.id) else {
+            continue;
+          };
+
+          if !idea.deleted && !edits.is_empty() {
+            info!("Handling an edited idea... ({:?})", idea.id);
+            for handler in self.handlers_for(&routes, Some(idea)) {
+              if let Err(err) = handler.handle_idea_edited(idea, edits).await {
+                error!("Failed to handle edited idea: {:?}", err);
+              } else {
+                debug!("Successfully handled edited idea: {:?}", idea.id);
+              }
+            }
+          }
+
+          if !idea.deleted
+            && (old_idea.votecount != idea.votecount
+              || old_idea.comment_count != idea.comment_count)
+          {
+            info!("Handling an idea activity update... ({:?})", idea.id);
+            for handler in self.handlers_for(&routes, Some(idea)) {
+              if let Err(err) = handler.handle_idea_activity(idea).await {
+                error!("Failed to handle idea activity: {:?}", err);
+              } else {
+                debug!("Successfully handled idea activity: {:?}", idea.id);
+              }
+            }
+          }
+
+          // Ideas cached before these fields were tracked are only backfilled.
+          if old_idea.closed.is_none() {
+            continue;
+          }
+
+          if old_idea.closed == Some(false) && idea.closed == Some(true) {
+            info!("Handling a closed idea... ({:?})", idea.id);
+            for handler in self.handlers_for(&routes, Some(idea)) {
+              if let Err(err) = handler.handle_idea_closed(idea).await {
+                error!("Failed to handle closed idea: {:?}", err);
+              } else {
+                debug!("Successfully handled closed idea: {:?}", idea.id);
+              }
+            }
+          }
+
+          if let Some(threshold) = self.consensus_threshold {
+            let old_consensus = old_idea.consensus.unwrap_or_default();
+            let consensus = idea.consensus.unwrap_or_default();
+
+            if old_consensus < threshold && consensus >= threshold {
+              info!("Handling an idea reaching consensus... ({:?})", idea.id);
+              for handler in self.handlers_for(&routes, Some(idea)) {
+                if let Err(err) = handler.handle_idea_consensus(idea, threshold).await {
+                  error!("Failed to handle idea consensus: {:?}", err);
+                } else {
+                  debug!("Successfully handled idea consensus: {:?}", idea.id);
+                }
+              }
+            }
+          }
+        }
+
+        deleted_ideas = old_ideas
+          .iter()
+          .filter(|old_idea| {
+            !old_idea.deleted
+              && ideas
+                .iter()
+                .any(|idea| idea.id == old_idea.id && idea.deleted)
+          })
+          .cloned()
+          .collect();
+
+        debug!("Found {:?} deleted ideas.", deleted_ideas.len());
+
+        for idea in &deleted_ideas {
+          info!("Handling a deleted idea... ({:?})", idea.id);
+          for handler in self.handlers_for(&routes, Some(idea)) {
+            if let Err(err) = handler.handle_idea_deleted(idea).await {
+              error!("Failed to handle deleted idea: {:?}", err);
+            } else {
+              debug!("Successfully handled deleted idea: {:?}", idea.id);
+            }
+          }
+        }
       }
 
       if !new_ideas.is_empty() {
+        self.cache.put("prop_lot:ideas:channels", &routes).await;
+        info!("Updated idea channels in cache");
+      }
+
+      if !new_ideas.is_empty() || !changed_ideas.is_empty() || !deleted_ideas.is_empty() {
         self.cache.put("prop_lot:ideas", &ideas).await;
         info!("Updated ideas in cache");
       }
@@ -147,6 +615,12 @@ impl PropLot {
       warn!("Failed to fetch ideas");
     }
 
+    let ideas = self
+      .cache
+      .get::<Vec<Idea>>("prop_lot:ideas")
+      .await?
+      .unwrap_or_default();
+
     if let Some(votes) = self.fetcher.fetch_votes().await {
       debug!("Fetched {:?} votes.", votes.len());
 
@@ -162,16 +636,23 @@ impl PropLot {
 
         debug!("Found {:?} new votes.", new_votes.len());
 
-        for vote in &new_votes {
-          info!("Handling a new vote...");
-          for handler in &self.handlers {
-            if let Err(err) = handler.handle_new_vote(vote).await {
-              error!("Failed to handle new vote: {:?}", err);
-            } else {
-              debug!("Successfully handled new vote: {:?}", vote.id);
+        if self.vote_messages_enabled {
+          for vote in &new_votes {
+            info!("Handling a new vote...");
+            let idea = ideas.iter().find(|idea| idea.id == vote.idea_id);
+            for handler in self.handlers_for(&routes, idea) {
+              if let Err(err) = handler.handle_new_vote(vote).await {
+                error!("Failed to handle new vote: {:?}", err);
+              } else {
+                debug!("Successfully handled new vote: {:?}", vote.id);
+              }
             }
           }
         }
+
+        if !new_votes.is_empty() {
+          self.check_vote_milestones(&ideas, &votes, &routes).await?;
+        }
       }
 
       if !new_votes.is_empty() {
@@ -186,30 +667,88 @@ impl PropLot {
       debug!("Fetched {:?} comments.", comments.len());
 
       let mut new_comments = Vec::new();
+      let mut edited_comments = Vec::new();
+      let mut deleted_comments = Vec::new();
 
       if let Some(old_comments) = self.cache.get::<Vec<Comment>>("prop_lot:comments").await? {
         let old_ids: Vec<_> = old_comments.iter().map(|comment| &comment.id).collect();
         new_comments = comments
           .iter()
-          .filter(|comment| !old_ids.contains(&&comment.id))
+          .filter(|comment| !old_ids.contains(&&comment.id) && !comment.deleted)
           .cloned()
           .collect();
 
+        // Parents are handled before their replies so replies can be threaded.
+        new_comments.sort_by_key(|comment| comment.id);
+
         debug!("Found {:?} new comments.", new_comments.len());
 
         for comment in &new_comments {
           info!("Handling a new comment...");
-          for handler in &self.handlers {
-            if let Err(err) = handler.handle_new_comment(comment).await {
+          let parent = comment
+            .parent_id
+            .and_then(|parent_id| comments.iter().find(|parent| parent.id == parent_id));
+          let idea = ideas.iter().find(|idea| idea.id == comment.idea_id);
+          for handler in self.handlers_for(&routes, idea) {
+            if let Err(err) = handler.handle_new_comment(comment, parent).await {
               error!("Failed to handle new comment: {:?}", err);
             } else {
               debug!("Successfully handled new comment: {:?}", comment.id);
             }
           }
         }
+
+        edited_comments = comments
+          .iter()
+          .filter(|comment| !comment.deleted)
+          .filter_map(|comment| {
+            let old_comment = old_comments.iter().find(|old| old.id == comment.id)?;
+            let edits = comment.edits_since(old_comment);
+            (!edits.is_empty()).then(|| (comment.clone(), edits))
+          })
+          .collect();
+
+        debug!("Found {:?} edited comments.", edited_comments.len());
+
+        for (comment, edits) in &edited_comments {
+          info!("Handling an edited comment... ({:?})", comment.id);
+          let idea = ideas.iter().find(|idea| idea.id == comment.idea_id);
+          for handler in self.handlers_for(&routes, idea) {
+            if let Err(err) = handler.handle_comment_edited(comment, edits).await {
+              error!("Failed to handle edited comment: {:?}", err);
+            } else {
+              debug!("Successfully handled edited comment: {:?}", comment.id);
+            }
+          }
+        }
+
+        deleted_comments = old_comments
+          .iter()
+          .filter(|old_comment| {
+            !old_comment.deleted
+              && comments
+                .iter()
+                .any(|comment| comment.id == old_comment.id && comment.deleted)
+          })
+          .cloned()
+          .collect();
+
+        debug!("Found {:?} deleted comments.", deleted_comments.len());
+
+        for comment in &deleted_comments {
+          info!("Handling a deleted comment... ({:?})", comment.id);
+          let idea = ideas.iter().find(|idea| idea.id == comment.idea_id);
+          for handler in self.handlers_for(&routes, idea) {
+            if let Err(err) = handler.handle_comment_deleted(comment).await {
+              error!("Failed to handle deleted comment: {:?}", err);
+            } else {
+              debug!("Successfully handled deleted comment: {:?}", comment.id);
+            }
+          }
+        }
       }
 
-      if !new_comments.is_empty() {
+      if !new_comments.is_empty() || !edited_comments.is_empty() || !deleted_comments.is_empty() {
         self.cache.put("prop_lot:comments", &comments).await;
         info!("Updated comments in cache");
       }
@@ -217,8 +756,134 @@ impl PropLot {
       warn!("Failed to fetch comments");
     }
 
+    if self.leaderboard_enabled {
+      self.post_leaderboard(&ideas, &routes).await?;
+    }
+
     debug!("Start function finished.");
 
     Ok(())
   }
+
+  /// Posts the activity since the previous leaderboard once a week has passed,
+  /// then records the current totals for the next one.
+  async fn post_leaderboard(
+    &self,
+    ideas: &[Idea],
+    routes: &HashMap<String, Vec<String>>,
+  ) -> Result<()> {
+    let now = Utc::now().timestamp();
+
+    let snapshot = self
+      .cache
+      .get::<LeaderboardSnapshot>("prop_lot:leaderboard")
+      .await?;
+
+    if snapshot
+      .as_ref()
+      .is_some_and(|snapshot| now - snapshot.created < LEADERBOARD_PERIOD)
+    {
+      return Ok(());
+    }
+
+    let Some(user_activity) = self.fetcher.fetch_user_activity().await else {
+      warn!("Failed to fetch user activity");
+      return Ok(());
+    };
+
+    if let Some(snapshot) = snapshot {
+      let leaderboard = Leaderboard::since(&snapshot, ideas, &user_activity);
+
+      info!("Handling a weekly leaderboard...");
+      for handler in self.handlers_for(routes, None) {
+        if let Err(err) = handler.handle_leaderboard(&leaderboard).await {
+          error!("Failed to handle leaderboard: {:?}", err);
+        } else {
+          debug!("Successfully handled leaderboard");
+        }
+      }
+    }
+
+    let snapshot = LeaderboardSnapshot {
+      created: now,
+      idea_votes: ideas
+        .iter()
+        .map(|idea| (idea.id.to_string(), idea.votecount))
+        .collect(),
+      user_activity: user_activity
+        .into_iter()
+        .map(|user| (user.wallet.clone(), user))
+        .collect(),
+    };
+
+    self.cache.put("prop_lot:leaderboard", &snapshot).await;
+    info!("Updated leaderboard snapshot in cache");
+
+    Ok(())
+  }
+
+  /// Announces ideas whose net weighted votes reach a configured milestone,
+  /// once per milestone and only for the highest milestone reached at once.
+  async fn check_vote_milestones(
+    &self,
+    ideas: &[Idea],
+    votes: &[Vote],
+    routes: &HashMap<String, Vec<String>>,
+  ) -> Result<()> {
+    if self.vote_milestones.is_empty() {
+      return Ok(());
+    }
+
+    let cached_milestones = self
+      .cache
+      .get::<HashMap<String, Vec<isize>>>("prop_lot:ideas:milestones")
+      .await?;
+    // Milestones reached before they were tracked are only recorded.
+    let backfill = cached_milestones.is_none();
+    let mut milestones = cached_milestones.unwrap_or_default();
+    let mut updated = false;
+
+    for (idea_id, net) in net_votes(votes) {
+      let reached = milestones.entry(idea_id.to_string()).or_default();
+      let due: Vec<_> = self
+        .vote_milestones
+        .iter()
+        .filter(|milestone| net >= **milestone && !reached.contains(milestone))
+        .copied()
+        .collect();
+
+      let Some(milestone) = due.iter().max().copied() else {
+        continue;
+      };
+      reached.extend(due);
+      updated = true;
+
+      if backfill {
+        continue;
+      }
+
+      let Some(idea) = ideas.iter().find(|idea| idea.id == idea_id) else {
+        continue;
+      };
+
+      info!("Handling a vote milestone... ({:?})", idea.id);
+      for handler in self.handlers_for(routes, Some(idea)) {
+        if let Err(err) = handler.handle_vote_milestone(idea, milestone, net).await {
+          error!("Failed to handle vote milestone: {:?}", err);
+        } else {
+          debug!("Successfully handled vote milestone: {:?}", idea.id);
+        }
+      }
+    }
+
+    if updated || backfill {
+      self
+        .cache
+        .put("prop_lot:ideas:milestones", &milestones)
+        .await;
+      info!("Updated idea milestones in cache");
+    }
+
+    Ok(())
+  }
 }
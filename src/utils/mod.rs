@@ -2,6 +2,7 @@ use cfg_if::cfg_if;
 
 pub(crate) mod ens;
 pub(crate) mod link;
+pub(crate) mod text;
 
 cfg_if! {
     // https://github.com/rustwasm/console_error_panic_hook#readme
@@ -34,3 +35,44 @@ pub fn get_short_address(address: &str) -> String {
 pub fn get_explorer_address(address: &str) -> String {
   format!("https://etherscan.io/address/{}", address)
 }
+
+/// Builds the URL path segment prop.house uses for a community or round name.
+///
+/// Follows the prop.house web app `nameToSlug`, which replaces each space with
+/// a hyphen and lowercases the name, keeping any other character as is. Those
+/// characters are percent-encoded here so the link stays valid once posted.
+pub fn get_slug(name: &str) -> String {
+  let mut slug = String::new();
+
+  for char in name.replace(' ', "-").to_lowercase().chars() {
+    if char.is_ascii_alphanumeric() || matches!(char, '-' | '.' | '_' | '~') {
+      slug.push(char);
+    } else {
+      let mut buffer = [0; 4];
+      for byte in char.encode_utf8(&mut buffer).bytes() {
+        slug.push_str(&format!("%{:02X}", byte));
+      }
+    }
+  }
+
+  slug
+}
+
+#[cfg(test)]
+mod tests {
+  use super::get_slug;
+
+  #[test]
+  fn slugs_follow_name_to_slug() {
+    let vectors = [
+      ("Lil Nouns Community Round", "lil-nouns-community-round"),
+      ("Nounish Builders Round #2", "nounish-builders-round-%232"),
+      ("Lil Nouns x Nouns: Round 3", "lil-nouns-x-nouns%3A-round-3"),
+      ("Art  &  Culture", "art--%26--culture"),
+    ];
+
+    for (title, slug) in vectors {
+      assert_eq!(get_slug(title), slug, "slug of {:?}", title);
+    }
+  }
+}
@@ -0,0 +1,221 @@
+use std::collections::{HashMap, HashSet};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+const ELLIPSIS: &str = "...";
+
+/// Shortens text to at most `limit` characters, cutting between graphemes and
+/// ending with an ellipsis when anything was cut.
+pub fn truncate_chars(text: &str, limit: usize) -> String {
+  truncate_with(text, limit, |part| part.chars().count())
+}
+
+/// Shortens text to at most `limit` bytes, cutting between graphemes and ending
+/// with an ellipsis when anything was cut. Farcaster limits casts in bytes.
+pub fn truncate_bytes(text: &str, limit: usize) -> String {
+  truncate_with(text, limit, str::len)
+}
+
+fn truncate_with(text: &str, limit: usize, measure: impl Fn(&str) -> usize) -> String {
+  if measure(text) <= limit {
+    return text.to_string();
+  }
+
+  let Some(budget) = limit.checked_sub(measure(ELLIPSIS)) else {
+    return String::new();
+  };
+
+  let mut truncated = String::new();
+  let mut used = 0;
+
+  for grapheme in text.graphemes(true) {
+    let size = measure(grapheme);
+    if used + size > budget {
+      break;
+    }
+    truncated.push_str(grapheme);
+    used += size;
+  }
+
+  format!("{}{}", truncated.trim_end(), ELLIPSIS)
+}
+
+/// Markdown patterns and their replacements, compiled once.
+static MARKDOWN_REPLACEMENTS: Lazy<Vec<(Regex, &str)>> = Lazy::new(|| {
+  [
+    // Images and links keep their label.
+    (r"!\[([^\]]*)\]\([^)]*\)", "$1"),
+    (r"\[([^\]]*)\]\([^)]*\)", "$1"),
+    (r"<[^>]+>", ""),
+    (r"(?m)^```.*$", ""),
+    (r"`([^`]*)`", "$1"),
+    (r"(?m)^\s{0,3}#{1,6}\s+", ""),
+    (r"(?m)^\s{0,3}>\s?", ""),
+    (r"(?m)^\s*([-*_]\s*){3,}$", ""),
+    (r"(?m)^\s*[-*+]\s+", ""),
+    (r"\*\*([^*]+)\*\*", "$1"),
+    (r"__([^_]+)__", "$1"),
+    (r"\*([^*\s][^*]*)\*", "$1"),
+    (r"\b_([^_\s][^_]*)_\b", "$1"),
+    (r"~~([^~]+)~~", "$1"),
+  ]
+  .into_iter()
+  .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+  .collect()
+});
+
+/// Removes markdown syntax from user-written bodies, keeping their text, and
+/// collapses the remaining whitespace so bodies read well in short messages.
+pub fn strip_markdown(text: &str) -> String {
+  let mut stripped = text.to_string();
+  for (pattern, replacement) in MARKDOWN_REPLACEMENTS.iter() {
+    stripped = pattern.replace_all(&stripped, *replacement).to_string();
+  }
+
+  stripped.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Summarizes how many words were added to and removed from a text, such as
+/// "3 words added, 1 removed", or whether the same words were only reordered.
+pub fn summarize_diff(old: &str, new: &str) -> String {
+  let mut counts: HashMap<&str, isize> = HashMap::new();
+  for word in old.split_whitespace() {
+    *counts.entry(word).or_default() -= 1;
+  }
+  for word in new.split_whitespace() {
+    *counts.entry(word).or_default() += 1;
+  }
+
+  let added: isize = counts.values().filter(|count| **count > 0).sum();
+  let removed: isize = -counts.values().filter(|count| **count < 0).sum::<isize>();
+
+  let words = |count: isize| match count {
+    1 => "1 word".to_string(),
+    count => format!("{} words", count),
+  };
+
+  match (added, removed) {
+    (0, 0) if !old.split_whitespace().eq(new.split_whitespace()) => "words reordered".to_string(),
+    (0, 0) => "whitespace changed".to_string(),
+    (added, 0) => format!("{} added", words(added)),
+    (0, removed) => format!("{} removed", words(removed)),
+    (added, removed) => format!("{} added, {} removed", words(added), removed),
+  }
+}
+
+/// Scores how close two titles are, from 0 to 1, by the share of words they
+/// have in common, ignoring case and punctuation.
+pub fn title_similarity(first: &str, second: &str) -> f64 {
+  let words = |title: &str| -> HashSet<String> {
+    title
+      .split(|c: char| !c.is_alphanumeric())
+      .filter(|word| !word.is_empty())
+      .map(str::to_lowercase)
+      .collect()
+  };
+
+  let (first, second) = (words(first), words(second));
+  let union = first.union(&second).count();
+  if union == 0 {
+    return 0.0;
+  }
+
+  first.intersection(&second).count() as f64 / union as f64
+}
+
+/// Describes a duration in whole hours, or in minutes under an hour, such as
+/// "10 hours" or "1 minute".
+pub fn describe_duration(seconds: i64) -> String {
+  let hours = (seconds + 30 * 60) / (60 * 60);
+  let (count, unit) = if hours >= 1 {
+    (hours, "hour")
+  } else {
+    (((seconds + 30) / 60).max(1), "minute")
+  };
+
+  match count {
+    1 => format!("1 {}", unit),
+    count => format!("{} {}s", count, unit),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{
+    describe_duration,
+    strip_markdown,
+    summarize_diff,
+    title_similarity,
+    truncate_bytes,
+    truncate_chars,
+  };
+
+  #[test]
+  fn keeps_short_text() {
+    assert_eq!(truncate_chars("gm lils", 7), "gm lils");
+    assert_eq!(truncate_bytes("gm lils", 7), "gm lils");
+  }
+
+  #[test]
+  fn truncates_on_grapheme_boundaries() {
+    assert_eq!(truncate_chars("héllo wörld", 8), "héllo...");
+    assert_eq!(truncate_bytes("héllo wörld", 8), "héll...");
+    assert_eq!(truncate_bytes("👍🏽👍🏽👍🏽", 12), "👍🏽...");
+    assert_eq!(truncate_chars("👨‍👩‍👧 family", 6), "...");
+    assert_eq!(truncate_chars("abcdef", 2), "");
+  }
+
+  #[test]
+  fn strips_markdown() {
+    assert_eq!(
+      strip_markdown("## Summary\n\n**Fund** the [lil tour](https://lilnouns.wtf) ~~now~~!"),
+      "Summary Fund the lil tour now!"
+    );
+    assert_eq!(
+      strip_markdown("- one\n- `two`\n> quoted\n---\n![noggles](https://i.imgur.com/a.png)"),
+      "one two quoted noggles"
+    );
+    assert_eq!(
+      strip_markdown("keep snake_case names"),
+      "keep snake_case names"
+    );
+  }
+
+  #[test]
+  fn summarizes_diffs() {
+    assert_eq!(
+      summarize_diff("fund the lil tour", "fund the big lil tour now"),
+      "2 words added"
+    );
+    assert_eq!(
+      summarize_diff("fund the lil tour", "fund a lil party"),
+      "2 words added, 2 removed"
+    );
+    assert_eq!(summarize_diff("gm lils", "gm"), "1 word removed");
+    assert_eq!(summarize_diff("gm  lils", "gm lils"), "whitespace changed");
+    assert_eq!(summarize_diff("gm lils", "lils gm"), "words reordered");
+  }
+
+  #[test]
+  fn scores_title_similarity() {
+    assert_eq!(
+      title_similarity("Lil Tour: Round 2", "lil tour round 2"),
+      1.0
+    );
+    assert_eq!(
+      title_similarity("Fund the lil tour", "Fund the lil party"),
+      0.6
+    );
+    assert_eq!(title_similarity("", "Lil Tour"), 0.0);
+  }
+
+  #[test]
+  fn describes_durations() {
+    assert_eq!(describe_duration(10 * 60 * 60 - 5 * 60), "10 hours");
+    assert_eq!(describe_duration(60 * 60), "1 hour");
+    assert_eq!(describe_duration(20 * 60), "20 minutes");
+    assert_eq!(describe_duration(10), "1 minute");
+  }
+}